@@ -5,9 +5,9 @@ extern crate minifb;
 use blit::Color;
 use direct_gui::controls::*;
 use direct_gui::*;
-use minifb::*;
+use minifb::{Key, MouseButton, MouseMode, Window, WindowOptions};
 
-const WIDTH: usize = 120;
+const WIDTH: usize = 200;
 const HEIGHT: usize = 50;
 
 fn on_button_state_changed<S>(button: &mut Button<S>, state: ButtonState) {
@@ -39,10 +39,22 @@ fn main() {
             .with_pos(10, 10)
             .with_callback(on_button_state_changed),
     );
+    let mut clicks = 0;
     gui.register(
         Button::new((30, 30), Color::from_u32(0xFF_00_00))
             .with_pos(80, 10)
-            .with_callback(on_button_state_changed),
+            .with_callback(on_button_state_changed)
+            .with_click_callback(move |_| {
+                clicks += 1;
+                println!("Button clicked {} times", clicks);
+            }),
+    );
+    let default_font = gui.default_font();
+    gui.register(
+        Button::new_with_text(default_font, "Start", Color::from_u32(0x44_44_AA))
+            .with_pos(120, 10)
+            .with_text_color(ButtonState::Hover, Color::from_u32(0xFF_FF_66))
+            .with_click_callback(|button| button.set_text("Started")),
     );
 
     while window.is_open() && !window.is_key_down(Key::Escape) {
@@ -53,6 +65,12 @@ fn main() {
         if let Some(mouse) = window.get_mouse_pos(MouseMode::Pass) {
             cs.mouse_pos = (mouse.0 as i32, mouse.1 as i32);
             cs.mouse_down = window.get_mouse_down(MouseButton::Left);
+            cs.mouse_right_down = window.get_mouse_down(MouseButton::Right);
+            cs.mouse_middle_down = window.get_mouse_down(MouseButton::Middle);
+        }
+
+        if let Some(scroll) = window.get_scroll_wheel() {
+            cs.scroll_delta = scroll;
         }
 
         gui.update(&cs);
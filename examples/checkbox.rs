@@ -0,0 +1,65 @@
+extern crate blit;
+extern crate direct_gui;
+extern crate minifb;
+
+use blit::Color;
+use direct_gui::controls::*;
+use direct_gui::*;
+use minifb::{Key, MouseButton, MouseMode, Window, WindowOptions};
+
+const WIDTH: usize = 160;
+const HEIGHT: usize = 90;
+
+fn main() {
+    let mut buffer: Vec<u32> = vec![0x22_22_22; WIDTH * HEIGHT];
+
+    let mut window = Window::new(
+        "direct-gui checkbox example - ESC to exit",
+        WIDTH,
+        HEIGHT,
+        WindowOptions::default(),
+    )
+    .expect("Unable to open window");
+
+    let mut gui = Gui::new((WIDTH as i32, HEIGHT as i32));
+
+    let default_font = gui.default_font();
+    gui.register(
+        Checkbox::new((11, 11), Color::from_u32(0xCC_CC_CC))
+            .with_pos(10, 10)
+            .with_label(default_font, "Fullscreen"),
+    );
+    gui.register(
+        RadioGroup::new(default_font, (11, 11), Color::from_u32(0xCC_CC_CC))
+            .with_pos(10, 30)
+            .with_option("Easy")
+            .with_option("Normal")
+            .with_option("Hard")
+            .with_selected(1),
+    );
+
+    while window.is_open() && !window.is_key_down(Key::Escape) {
+        let mut cs = ControlState {
+            ..ControlState::default()
+        };
+
+        if let Some(mouse) = window.get_mouse_pos(MouseMode::Pass) {
+            cs.mouse_pos = (mouse.0 as i32, mouse.1 as i32);
+            cs.mouse_down = window.get_mouse_down(MouseButton::Left);
+        }
+
+        gui.update(&cs);
+
+        for event in gui.events() {
+            println!("{:?}", event.kind);
+        }
+
+        // Clear the background because the labels don't fill it
+        for pixel in buffer.iter_mut() {
+            *pixel = 0x22_22_22;
+        }
+        gui.draw_to_buffer(&mut buffer);
+
+        window.update_with_buffer(&buffer, WIDTH, HEIGHT).unwrap();
+    }
+}
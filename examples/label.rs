@@ -5,9 +5,7 @@ extern crate minifb;
 use blit::Color;
 use direct_gui::controls::*;
 use direct_gui::*;
-use minifb::*;
-
-use std::str::FromStr;
+use minifb::{Key, Window, WindowOptions};
 
 const WIDTH: usize = 400;
 const HEIGHT: usize = 160;
@@ -40,6 +38,7 @@ fn main() {
                 char_size: (9, 9),
                 leading_offset: 2,
                 mask_color: Color::from_u32(0xFF_00_FF),
+                ..FontSettings::default()
             },
         )
         .unwrap();
@@ -57,6 +56,7 @@ fn main() {
                 char_size: (9, 9),
                 leading_offset: 2,
                 mask_color: Color::from_u32(0xFF_00_FF),
+                ..FontSettings::default()
             },
         )
         .unwrap();
@@ -83,8 +83,7 @@ fn main() {
         gui.draw_label(
             &mut buffer,
             font,
-            &String::from_str("This label is not registered and\nis drawn directly every frame.")
-                .unwrap(),
+            "This label is not registered and\nis drawn directly every frame.",
             (10, 120),
         );
 
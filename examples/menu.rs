@@ -0,0 +1,136 @@
+extern crate blit;
+extern crate direct_gui;
+extern crate minifb;
+
+use blit::Color;
+use direct_gui::controls::*;
+use direct_gui::*;
+use minifb::{KeyRepeat, MouseButton, MouseMode, Window, WindowOptions};
+
+const WIDTH: usize = 160;
+const HEIGHT: usize = 120;
+
+/// Convert the keys used to navigate the menu from minifb to direct-gui.
+fn convert_key(key: minifb::Key) -> Option<Key> {
+    Some(match key {
+        minifb::Key::Tab => Key::Tab,
+        minifb::Key::Up => Key::Up,
+        minifb::Key::Down => Key::Down,
+        minifb::Key::Left => Key::Left,
+        minifb::Key::Right => Key::Right,
+        minifb::Key::Enter => Key::Enter,
+        minifb::Key::Space => Key::Space,
+        _ => return None,
+    })
+}
+
+/// Pretend W, A, S, D, J and K are the buttons of a gamepad.
+fn convert_gamepad(key: minifb::Key) -> Option<NavIntent> {
+    Some(match key {
+        minifb::Key::W => NavIntent::Up,
+        minifb::Key::S => NavIntent::Down,
+        minifb::Key::A => NavIntent::Left,
+        minifb::Key::D => NavIntent::Right,
+        minifb::Key::J => NavIntent::Accept,
+        minifb::Key::K => NavIntent::Cancel,
+        _ => return None,
+    })
+}
+
+fn main() {
+    let mut buffer: Vec<u32> = vec![0x22_22_22; WIDTH * HEIGHT];
+
+    let mut window = Window::new(
+        "direct-gui menu example - navigate with Tab, the arrow keys and Enter",
+        WIDTH,
+        HEIGHT,
+        WindowOptions::default(),
+    )
+    .expect("Unable to open window");
+
+    let mut gui = Gui::new((WIDTH as i32, HEIGHT as i32));
+    gui.set_layout(Some(
+        Layout::column()
+            .with_spacing(6)
+            .with_align(Alignment::Center, Alignment::Center),
+    ));
+
+    // Give the buttons a light border which turns white when they are hovered
+    let button_style = Style {
+        border_color: Color::from_u32(0x88_88_88),
+        border_width: 2,
+        ..Style::default()
+    };
+    gui.set_theme(
+        Theme::new()
+            .with_style(ControlType::Button, StyleState::Normal, button_style)
+            .with_style(
+                ControlType::Button,
+                StyleState::Hover,
+                Style {
+                    shade: Shade::Lighten(0x22_22_22),
+                    border_color: Color::from_u32(0xFF_FF_FF),
+                    ..button_style
+                },
+            ),
+    );
+
+    let default_font = gui.default_font();
+    let start = gui.register(Button::new_with_text(
+        default_font,
+        "Start game",
+        Color::from_u32(0x44_44_AA),
+    ));
+    gui.register(
+        Checkbox::new((11, 11), Color::from_u32(0xCC_CC_CC)).with_label(default_font, "Fullscreen"),
+    );
+    gui.register(Slider::new((100, 11), Color::from_u32(0xCC_CC_CC)).with_value(0.5));
+    let quit = gui.register(Button::new_with_text(
+        default_font,
+        "Quit",
+        Color::from_u32(0xAA_44_44),
+    ));
+
+    // Start with the first button focused so the menu can be used without a mouse
+    gui.set_focused(Some(start)).unwrap();
+
+    let mut running = true;
+    while running && window.is_open() {
+        let mut cs = ControlState {
+            ..ControlState::default()
+        };
+
+        if let Some(mouse) = window.get_mouse_pos(MouseMode::Pass) {
+            cs.mouse_pos = (mouse.0 as i32, mouse.1 as i32);
+            cs.mouse_down = window.get_mouse_down(MouseButton::Left);
+        }
+
+        if let Some(keys) = window.get_keys_pressed(KeyRepeat::Yes) {
+            cs.nav = keys.iter().cloned().filter_map(convert_gamepad).collect();
+            cs.keys = keys
+                .into_iter()
+                .filter_map(convert_key)
+                .map(KeyEvent::Pressed)
+                .collect();
+        }
+        cs.modifiers.shift = window.is_key_down(minifb::Key::LeftShift)
+            || window.is_key_down(minifb::Key::RightShift);
+
+        gui.update(&cs);
+
+        for event in gui.events() {
+            if event.control == quit && event.kind == GuiEventKind::Clicked {
+                running = false;
+            } else {
+                println!("{:?}", event.kind);
+            }
+        }
+
+        for pixel in buffer.iter_mut() {
+            *pixel = 0x22_22_22;
+        }
+        gui.draw_to_buffer(&mut buffer);
+
+        window.update_with_buffer(&buffer, WIDTH, HEIGHT).unwrap();
+    }
+}
@@ -0,0 +1,90 @@
+extern crate blit;
+extern crate direct_gui;
+extern crate minifb;
+
+use blit::Color;
+use direct_gui::controls::*;
+use direct_gui::*;
+use minifb::{Key, MouseButton, MouseMode, Window, WindowOptions};
+
+const WIDTH: usize = 200;
+const HEIGHT: usize = 120;
+
+fn main() {
+    let mut buffer: Vec<u32> = vec![0x22_22_22; WIDTH * HEIGHT];
+
+    let mut window = Window::new(
+        "direct-gui panel example - ESC to exit",
+        WIDTH,
+        HEIGHT,
+        WindowOptions::default(),
+    )
+    .expect("Unable to open window");
+
+    let mut gui = Gui::new((WIDTH as i32, HEIGHT as i32));
+
+    let default_font = gui.default_font();
+    let toggle = gui.register(
+        Button::new_with_text(default_font, "Toggle", Color::from_u32(0x44_44_AA)).with_pos(10, 10),
+    );
+    let panel = gui.register(
+        Panel::new((120, 70))
+            .with_pos(70, 40)
+            .with_color(Color::from_u32(0x33_33_33))
+            .with_layout(
+                Layout::column()
+                    .with_spacing(4)
+                    .with_padding(Insets::uniform(5)),
+            ),
+    );
+
+    // The children are positioned by the layout of the panel
+    gui.register_child(panel, Label::new(default_font).with_text("Options"))
+        .unwrap();
+    gui.register_child(
+        panel,
+        Checkbox::new((11, 11), Color::from_u32(0xCC_CC_CC)).with_label(default_font, "Music"),
+    )
+    .unwrap();
+    // This slider is partially outside of the panel and is cut off
+    gui.register_child(panel, Slider::new((150, 11), Color::from_u32(0xCC_CC_CC)))
+        .unwrap();
+
+    // Fade the panel in and out instead of showing and hiding it at once
+    let mut shown = true;
+
+    while window.is_open() && !window.is_key_down(Key::Escape) {
+        let mut cs = ControlState {
+            ..ControlState::default()
+        };
+
+        if let Some(mouse) = window.get_mouse_pos(MouseMode::Pass) {
+            cs.mouse_pos = (mouse.0 as i32, mouse.1 as i32);
+            cs.mouse_down = window.get_mouse_down(MouseButton::Left);
+        }
+
+        gui.update(&cs);
+
+        let events: Vec<GuiEvent> = gui.events().collect();
+        for event in events {
+            if event.control == toggle && event.kind == GuiEventKind::Clicked {
+                shown = !shown;
+            } else {
+                println!("{:?}", event.kind);
+            }
+        }
+
+        let step = if shown { 0.05 } else { -0.05 };
+        gui.set_opacity(panel, gui.opacity(panel) + step).unwrap();
+        // Hide it when it's faded out so it doesn't receive input anymore
+        gui.set_visible(panel, gui.opacity(panel) > 0.0).unwrap();
+
+        // Clear the background because the panel doesn't always fill it
+        for pixel in buffer.iter_mut() {
+            *pixel = 0x22_22_22;
+        }
+        gui.draw_to_buffer(&mut buffer);
+
+        window.update_with_buffer(&buffer, WIDTH, HEIGHT).unwrap();
+    }
+}
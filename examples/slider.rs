@@ -0,0 +1,109 @@
+extern crate blit;
+extern crate direct_gui;
+extern crate minifb;
+
+use blit::Color;
+use direct_gui::controls::*;
+use direct_gui::*;
+use minifb::{KeyRepeat, MouseButton, MouseMode, Window, WindowOptions};
+
+const WIDTH: usize = 200;
+const HEIGHT: usize = 90;
+
+/// Convert the keys used by the sliders from minifb to direct-gui.
+fn convert_key(key: minifb::Key) -> Option<Key> {
+    Some(match key {
+        minifb::Key::Left => Key::Left,
+        minifb::Key::Right => Key::Right,
+        minifb::Key::Up => Key::Up,
+        minifb::Key::Down => Key::Down,
+        minifb::Key::PageUp => Key::PageUp,
+        minifb::Key::PageDown => Key::PageDown,
+        minifb::Key::Home => Key::Home,
+        minifb::Key::End => Key::End,
+        _ => return None,
+    })
+}
+
+fn main() {
+    let mut buffer: Vec<u32> = vec![0x22_22_22; WIDTH * HEIGHT];
+
+    let mut window = Window::new(
+        "direct-gui slider & progress bar example - ESC to exit",
+        WIDTH,
+        HEIGHT,
+        WindowOptions::default(),
+    )
+    .expect("Unable to open window");
+
+    let mut gui = Gui::new((WIDTH as i32, HEIGHT as i32));
+
+    let volume = gui.register(
+        Slider::new((120, 11), Color::from_u32(0xCC_CC_CC))
+            .with_pos(10, 10)
+            .with_range(0.0, 100.0)
+            .with_step(1.0)
+            .with_value(50.0),
+    );
+    gui.register(
+        Slider::new((120, 11), Color::from_u32(0x44_AA_44))
+            .with_pos(10, 30)
+            .with_value(0.25),
+    );
+    let default_font = gui.default_font();
+    let bar = gui.register(
+        ProgressBar::new((120, 13), Color::from_u32(0xAA_22_22))
+            .with_pos(10, 50)
+            .with_max(100.0)
+            .with_value(50.0)
+            .with_text(default_font, "50"),
+    );
+    let scrollbar = gui.register(
+        Scrollbar::new((11, 70), Color::from_u32(0xCC_CC_CC), 300, 70)
+            .with_pos(180, 10)
+            .with_orientation(Orientation::Vertical),
+    );
+
+    while window.is_open() && !window.is_key_down(minifb::Key::Escape) {
+        let mut cs = ControlState {
+            ..ControlState::default()
+        };
+
+        if let Some(mouse) = window.get_mouse_pos(MouseMode::Pass) {
+            cs.mouse_pos = (mouse.0 as i32, mouse.1 as i32);
+            cs.mouse_down = window.get_mouse_down(MouseButton::Left);
+        }
+        if let Some(scroll) = window.get_scroll_wheel() {
+            cs.scroll_delta = scroll;
+        }
+
+        if let Some(keys) = window.get_keys_pressed(KeyRepeat::Yes) {
+            cs.keys = keys
+                .into_iter()
+                .filter_map(convert_key)
+                .map(KeyEvent::Pressed)
+                .collect();
+        }
+
+        gui.update(&cs);
+
+        let events: Vec<GuiEvent> = gui.events().collect();
+        for event in events {
+            if let GuiEventKind::ValueChanged(value) = event.kind {
+                if event.control == volume {
+                    let bar = gui.get_mut::<ProgressBar<Flat>>(bar).unwrap();
+                    bar.set_value(value);
+                    bar.set_text(&value.to_string());
+                } else if event.control == scrollbar {
+                    println!("Scrolled to {}", value);
+                } else {
+                    println!("Value {:.2}", value);
+                }
+            }
+        }
+
+        gui.draw_to_buffer(&mut buffer);
+
+        window.update_with_buffer(&buffer, WIDTH, HEIGHT).unwrap();
+    }
+}
@@ -0,0 +1,79 @@
+extern crate blit;
+extern crate direct_gui;
+extern crate minifb;
+
+use blit::Color;
+use direct_gui::controls::*;
+use direct_gui::*;
+use minifb::{Key, MouseButton, MouseMode, Window, WindowOptions};
+
+const WIDTH: usize = 320;
+const HEIGHT: usize = 80;
+
+/// The size of the viewport of a single player.
+const VIEWPORT: (i32, i32) = (WIDTH as i32 / 2, HEIGHT as i32);
+
+fn main() {
+    let mut buffer: Vec<u32> = vec![0; WIDTH * HEIGHT];
+
+    let mut window = Window::new(
+        "direct-gui split screen example - ESC to exit",
+        WIDTH,
+        HEIGHT,
+        WindowOptions::default(),
+    )
+    .expect("Unable to open window");
+
+    // Every player has their own GUI in half of the window
+    let viewports = [
+        Rect::from_pos_size((0, 0), VIEWPORT),
+        Rect::from_pos_size((VIEWPORT.0, 0), VIEWPORT),
+    ];
+    let mut guis: Vec<Gui> = ["Player 1", "Player 2"]
+        .iter()
+        .map(|name| {
+            let mut gui = Gui::new(VIEWPORT);
+            let default_font = gui.default_font();
+            gui.register(Label::new(default_font).with_text(name).with_pos(10, 10));
+            gui.register(
+                Button::new_with_text(default_font, "Ready", Color::from_u32(0x44_44_AA))
+                    .with_pos(10, 30),
+            );
+
+            gui
+        })
+        .collect();
+
+    while window.is_open() && !window.is_key_down(Key::Escape) {
+        let mouse = window.get_mouse_pos(MouseMode::Pass);
+        let mouse_down = window.get_mouse_down(MouseButton::Left);
+
+        // Clear the background of both viewports
+        for pixel in buffer.iter_mut() {
+            *pixel = 0x22_22_22;
+        }
+
+        let mut canvas = Canvas::new(&mut buffer, WIDTH, HEIGHT);
+        for (gui, viewport) in guis.iter_mut().zip(viewports.iter()) {
+            // The mouse position is relative to the viewport
+            let cs = ControlState {
+                mouse_pos: mouse.map_or((-1, -1), |(x, y)| {
+                    (x as i32 - viewport.x, y as i32 - viewport.y)
+                }),
+                mouse_down,
+                ..ControlState::default()
+            };
+
+            gui.update(&cs);
+            for event in gui.events() {
+                if event.kind == GuiEventKind::Clicked {
+                    println!("Player at {:?} is ready", viewport.pos());
+                }
+            }
+
+            gui.draw_to_canvas(&mut canvas.region(*viewport));
+        }
+
+        window.update_with_buffer(&buffer, WIDTH, HEIGHT).unwrap();
+    }
+}
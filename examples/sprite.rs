@@ -5,7 +5,7 @@ extern crate minifb;
 use blit::Color;
 use direct_gui::controls::*;
 use direct_gui::*;
-use minifb::*;
+use minifb::{Key, MouseButton, MouseMode, Window, WindowOptions};
 
 const WIDTH: usize = 120;
 const HEIGHT: usize = 80;
@@ -0,0 +1,112 @@
+extern crate direct_gui;
+extern crate minifb;
+
+use direct_gui::controls::*;
+use direct_gui::*;
+use minifb::{InputCallback, KeyRepeat, MouseButton, MouseMode, Window, WindowOptions};
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+const WIDTH: usize = 200;
+const HEIGHT: usize = 60;
+
+/// Collects the typed characters from the window.
+struct TypedText(Rc<RefCell<String>>);
+
+impl InputCallback for TypedText {
+    fn add_char(&mut self, uni_char: u32) {
+        if let Some(ch) = std::char::from_u32(uni_char) {
+            self.0.borrow_mut().push(ch);
+        }
+    }
+}
+
+/// Convert the keys used by the text input from minifb to direct-gui.
+fn convert_key(key: minifb::Key) -> Option<Key> {
+    Some(match key {
+        minifb::Key::A => Key::A,
+        minifb::Key::Left => Key::Left,
+        minifb::Key::Right => Key::Right,
+        minifb::Key::Up => Key::Up,
+        minifb::Key::Down => Key::Down,
+        minifb::Key::Home => Key::Home,
+        minifb::Key::End => Key::End,
+        minifb::Key::Backspace => Key::Backspace,
+        minifb::Key::Delete => Key::Delete,
+        minifb::Key::Enter => Key::Enter,
+        minifb::Key::Escape => Key::Escape,
+        minifb::Key::Tab => Key::Tab,
+        _ => return None,
+    })
+}
+
+fn main() {
+    let mut buffer: Vec<u32> = vec![0x22_22_22; WIDTH * HEIGHT];
+
+    let mut window = Window::new(
+        "direct-gui text input example - close window to exit",
+        WIDTH,
+        HEIGHT,
+        WindowOptions::default(),
+    )
+    .expect("Unable to open window");
+
+    let typed_text = Rc::new(RefCell::new(String::new()));
+    window.set_input_callback(Box::new(TypedText(typed_text.clone())));
+
+    let mut gui = Gui::new((WIDTH as i32, HEIGHT as i32));
+
+    let default_font = gui.default_font();
+    gui.register(
+        TextInput::new(default_font, 180)
+            .with_pos(10, 10)
+            .with_max_length(32)
+            .with_placeholder("Player name"),
+    );
+    gui.register(
+        TextInput::new(default_font, 180)
+            .with_pos(10, 32)
+            .with_password_mask('*')
+            .with_placeholder("Password"),
+    );
+
+    while window.is_open() {
+        let mut cs = ControlState {
+            ..ControlState::default()
+        };
+
+        if let Some(mouse) = window.get_mouse_pos(MouseMode::Pass) {
+            cs.mouse_pos = (mouse.0 as i32, mouse.1 as i32);
+            cs.mouse_down = window.get_mouse_down(MouseButton::Left);
+        }
+
+        if let Some(keys) = window.get_keys_pressed(KeyRepeat::Yes) {
+            cs.keys = keys
+                .into_iter()
+                .filter_map(convert_key)
+                .map(KeyEvent::Pressed)
+                .collect();
+        }
+        cs.modifiers = Modifiers {
+            shift: window.is_key_down(minifb::Key::LeftShift)
+                || window.is_key_down(minifb::Key::RightShift),
+            control: window.is_key_down(minifb::Key::LeftCtrl)
+                || window.is_key_down(minifb::Key::RightCtrl),
+            ..Modifiers::default()
+        };
+        cs.text = typed_text.replace(String::new());
+
+        gui.update(&cs);
+
+        for event in gui.events() {
+            if let GuiEventKind::Submitted(text) = event.kind {
+                println!("Submitted \"{}\"", text);
+            }
+        }
+
+        gui.draw_to_buffer(&mut buffer);
+
+        window.update_with_buffer(&buffer, WIDTH, HEIGHT).unwrap();
+    }
+}
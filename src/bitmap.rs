@@ -0,0 +1,116 @@
+use blit::{BlitBuffer, Color};
+
+/// An image with a transparency for every pixel, used for sprites, fonts and nine-slices.
+///
+/// The pixels are stored row by row as `0xAARRGGBB`, the alpha isn't premultiplied. An alpha of
+/// `0xFF` is opaque and `0x00` is fully transparent.
+///
+/// ```rust
+/// use direct_gui::controls::Sprite;
+/// use direct_gui::*;
+///
+/// let mut gui = Gui::new((800, 600));
+///
+/// // A white line that fades out to the right
+/// let pixels: Vec<u32> = (0..16).map(|x| ((255 - x * 16) << 24) | 0xFF_FF_FF).collect();
+/// let line = gui.load_sprite_from_bitmap(Bitmap::from_argb(&pixels, 16));
+///
+/// gui.register(Sprite::new_with_sprite(line).with_pos(10, 10));
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Bitmap {
+    width: i32,
+    height: i32,
+    pixels: Vec<u32>,
+}
+
+impl Bitmap {
+    /// Create a bitmap from pixels with an alpha channel, rows that aren't complete are dropped.
+    pub fn from_argb(pixels: &[u32], width: i32) -> Self {
+        let width = width.max(0);
+        let height = (pixels.len() as i32).checked_div(width).unwrap_or(0);
+
+        Bitmap {
+            width,
+            height,
+            pixels: pixels[..(width * height) as usize].to_vec(),
+        }
+    }
+
+    /// Create an opaque bitmap from pixels without an alpha channel, the pixels with the mask
+    /// color are transparent.
+    pub fn from_rgb(pixels: &[u32], width: i32, mask_color: Color) -> Self {
+        let mask = mask_color.u32() & 0xFF_FF_FF;
+        let pixels: Vec<u32> = pixels
+            .iter()
+            .map(|&pixel| match pixel & 0xFF_FF_FF {
+                rgb if rgb == mask => 0,
+                rgb => rgb | 0xFF_00_00_00,
+            })
+            .collect();
+
+        Bitmap::from_argb(&pixels, width)
+    }
+
+    /// Convert a blit buffer, the pixels it masks are transparent.
+    pub fn from_blit_buffer(buffer: &BlitBuffer) -> Self {
+        let (width, height) = buffer.size();
+        if width <= 0 || height <= 0 {
+            return Bitmap::from_argb(&[], 0);
+        }
+
+        // Blitting on the mask color only leaves the masked pixels with the mask color
+        let mask = buffer.mask_color().u32();
+        let mut pixels = vec![mask; (width * height) as usize];
+        buffer.blit(&mut pixels, width as usize, (0, 0));
+
+        for pixel in pixels.iter_mut() {
+            *pixel = if *pixel == mask {
+                0
+            } else {
+                *pixel | 0xFF_00_00_00
+            };
+        }
+
+        Bitmap {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// Get the size of the bitmap in pixels.
+    pub fn size(&self) -> (i32, i32) {
+        (self.width, self.height)
+    }
+
+    /// Get the width of the bitmap in pixels.
+    pub fn width(&self) -> i32 {
+        self.width
+    }
+
+    /// Get the height of the bitmap in pixels.
+    pub fn height(&self) -> i32 {
+        self.height
+    }
+
+    /// The pixels row by row as `0xAARRGGBB`.
+    pub fn pixels(&self) -> &[u32] {
+        &self.pixels
+    }
+
+    /// The pixel at a position, `None` when it's outside of the bitmap.
+    pub fn pixel(&self, x: i32, y: i32) -> Option<u32> {
+        if x < 0 || y < 0 || x >= self.width || y >= self.height {
+            return None;
+        }
+
+        Some(self.pixels[(x + y * self.width) as usize])
+    }
+}
+
+impl From<BlitBuffer> for Bitmap {
+    fn from(buffer: BlitBuffer) -> Self {
+        Bitmap::from_blit_buffer(&buffer)
+    }
+}
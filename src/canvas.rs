@@ -0,0 +1,544 @@
+use blit::Color;
+
+use super::bitmap::Bitmap;
+use super::pixel::PixelBuffer;
+use super::rect::{Insets, Rect};
+
+/// A buffer to draw on, all drawing operations are clipped to its bounds so controls can be
+/// partially or completely outside of it.
+///
+/// A canvas can also be a region of another canvas, positions are then relative to the top left
+/// corner of the region and drawing is clipped to it.
+///
+/// Sprites are blended with the pixels below them by their alpha. The pixels of the buffer can
+/// have any format of `PixelBuffer`, the colors are converted when they are drawn.
+pub struct Canvas<'a> {
+    buffer: PixelBuffer<'a>,
+    /// The amount of pixels from the start of a row to the start of the next row.
+    stride: usize,
+
+    /// The size of the region.
+    size: (i32, i32),
+    /// The position of the top left corner of the region in the buffer.
+    offset: (i32, i32),
+    /// The part of the buffer which can be drawn on.
+    clip: Rect,
+}
+
+impl<'a> Canvas<'a> {
+    /// Create a canvas for a buffer, the height is limited to the rows that fit in the buffer.
+    pub fn new<B>(buffer: B, width: usize, height: usize) -> Self
+    where
+        B: Into<PixelBuffer<'a>>,
+    {
+        Canvas::with_stride(buffer, width, height, width)
+    }
+
+    /// Create a canvas for a buffer where the rows are `stride` pixels apart, for example a
+    /// buffer with padding at the end of every row.
+    ///
+    /// Only the first `width` pixels of every row are drawn on and the height is limited to the
+    /// rows that fit in the buffer.
+    ///
+    /// ```rust
+    /// use direct_gui::*;
+    ///
+    /// // A framebuffer split into two viewports of 320 by 240 pixels
+    /// let mut buffer = vec![0u32; 640 * 240];
+    /// let mut canvas = Canvas::with_stride(&mut buffer, 640, 240, 640);
+    ///
+    /// let mut left = Gui::new((320, 240));
+    /// let mut right = Gui::new((320, 240));
+    /// left.draw_to_canvas(&mut canvas.region(Rect::new(0, 0, 320, 240)));
+    /// right.draw_to_canvas(&mut canvas.region(Rect::new(320, 0, 320, 240)));
+    /// ```
+    pub fn with_stride<B>(buffer: B, width: usize, height: usize, stride: usize) -> Self
+    where
+        B: Into<PixelBuffer<'a>>,
+    {
+        let buffer = buffer.into();
+        let stride = stride.max(width);
+
+        // The last row only needs to be as long as the width
+        let rows = match buffer.len().checked_sub(width) {
+            Some(rest) if stride > 0 => rest / stride + 1,
+            _ => 0,
+        };
+        let size = (width as i32, height.min(rows) as i32);
+
+        Canvas {
+            buffer,
+            stride,
+            size,
+            offset: (0, 0),
+            clip: Rect::from_pos_size((0, 0), size),
+        }
+    }
+
+    /// A canvas for drawing inside a rectangle of this canvas.
+    ///
+    /// The position `(0, 0)` of the new canvas is the top left corner of the rectangle and
+    /// nothing can be drawn outside of it.
+    pub fn region(&mut self, rect: Rect) -> Canvas<'_> {
+        let offset = (self.offset.0 + rect.x, self.offset.1 + rect.y);
+
+        Canvas {
+            buffer: self.buffer.reborrow(),
+            stride: self.stride,
+            size: rect.size(),
+            offset,
+            clip: self
+                .clip
+                .intersection(Rect::from_pos_size(offset, rect.size())),
+        }
+    }
+
+    /// Draw on the canvas and blend everything drawn inside the rectangle with what was below
+    /// it as a single layer.
+    ///
+    /// The opacity is from `0.0`, invisible, to `1.0`. Overlapping drawings in the layer don't
+    /// show through each other, and nothing is drawn outside of the rectangle.
+    pub fn draw_layer<F>(&mut self, rect: Rect, opacity: f32, draw: F)
+    where
+        F: FnOnce(&mut Canvas),
+    {
+        let alpha = (opacity.clamp(0.0, 1.0) * 255.0).round() as u32;
+        let area = rect
+            .translate(self.offset.0, self.offset.1)
+            .intersection(self.clip);
+        if area.is_empty() || alpha == 0 {
+            return;
+        }
+
+        let stride = self.stride;
+        let row_range = |y: i32| {
+            let start = area.x as usize + y as usize * stride;
+
+            start..start + area.width as usize
+        };
+
+        // Remember what's below the layer to blend it with afterwards
+        let below: Vec<u32> = (area.y..area.bottom())
+            .flat_map(row_range)
+            .map(|index| self.buffer.get(index))
+            .collect();
+
+        draw(&mut Canvas {
+            buffer: self.buffer.reborrow(),
+            stride: self.stride,
+            size: self.size,
+            offset: self.offset,
+            clip: area,
+        });
+
+        if alpha == 0xFF {
+            return;
+        }
+
+        let indices = (area.y..area.bottom()).flat_map(row_range);
+        for (index, below) in indices.zip(below) {
+            // Leave the pixels which are not drawn on untouched
+            let pixel = self.buffer.get(index);
+            if pixel != below {
+                self.buffer.set(index, blend(below, pixel, alpha));
+            }
+        }
+    }
+
+    /// The size of the area that can be drawn on.
+    pub fn size(&self) -> (i32, i32) {
+        self.size
+    }
+
+    /// The part of the area that can be drawn on which is inside the buffer.
+    pub fn bounds(&self) -> Rect {
+        self.clip.translate(-self.offset.0, -self.offset.1)
+    }
+
+    /// Fill a rectangle with a single color.
+    pub fn fill_rect(&mut self, rect: Rect, color: Color) {
+        let rect = rect
+            .translate(self.offset.0, self.offset.1)
+            .intersection(self.clip);
+        if rect.is_empty() {
+            return;
+        }
+
+        let color = color.u32();
+        for y in rect.y..rect.bottom() {
+            let start = rect.x as usize + y as usize * self.stride;
+            self.buffer.fill(start..start + rect.width as usize, color);
+        }
+    }
+
+    /// Draw the outline of a rectangle with a width of 1 pixel.
+    pub fn stroke_rect(&mut self, rect: Rect, color: Color) {
+        if rect.is_empty() {
+            return;
+        }
+
+        self.fill_rect(Rect::new(rect.x, rect.y, rect.width, 1), color);
+        self.fill_rect(Rect::new(rect.x, rect.bottom() - 1, rect.width, 1), color);
+        self.fill_rect(Rect::new(rect.x, rect.y, 1, rect.height), color);
+        self.fill_rect(Rect::new(rect.right() - 1, rect.y, 1, rect.height), color);
+    }
+
+    /// Draw a section of a sprite with the top left corner at the position.
+    pub fn blit(&mut self, sprite: &Bitmap, pos: (i32, i32), sub_rect: Rect) {
+        self.blit_with(sprite, pos, sub_rect, |pixel| pixel);
+    }
+
+    /// Draw a section of a sprite with every pixel multiplied by a color.
+    ///
+    /// A white pixel becomes the color and a black pixel stays black.
+    pub fn blit_tinted(&mut self, sprite: &Bitmap, pos: (i32, i32), sub_rect: Rect, tint: Color) {
+        let tint = tint.u32();
+
+        self.blit_with(sprite, pos, sub_rect, |pixel| multiply(pixel, tint));
+    }
+
+    /// Draw a section of a sprite stretched to a rectangle by repeating its parts.
+    ///
+    /// The corners defined by the insets are drawn as is, the edges are repeated along their
+    /// side and the center is repeated in both directions. When the rectangle is smaller than the
+    /// corners they are cut off.
+    pub fn blit_nine_slice(&mut self, sprite: &Bitmap, sub_rect: Rect, insets: Insets, rect: Rect) {
+        self.blit_nine_slice_with(
+            sprite,
+            sub_rect,
+            insets,
+            rect,
+            |canvas, sprite, src, dst| canvas.blit_tiled(sprite, src, dst, |pixel| pixel),
+        );
+    }
+
+    /// Draw a section of a sprite like `blit_nine_slice` with every pixel multiplied by a color.
+    pub fn blit_nine_slice_tinted(
+        &mut self,
+        sprite: &Bitmap,
+        sub_rect: Rect,
+        insets: Insets,
+        rect: Rect,
+        tint: Color,
+    ) {
+        let tint = tint.u32();
+
+        self.blit_nine_slice_with(
+            sprite,
+            sub_rect,
+            insets,
+            rect,
+            |canvas, sprite, src, dst| {
+                canvas.blit_tiled(sprite, src, dst, |pixel| multiply(pixel, tint))
+            },
+        );
+    }
+
+    /// Draw a section of a sprite stretched to a rectangle by scaling its parts.
+    ///
+    /// The corners defined by the insets are drawn as is, the edges are scaled along their side
+    /// and the center is scaled in both directions. Unlike `blit_nine_slice` a gradient doesn't
+    /// show seams, but the pixels are duplicated or skipped.
+    pub fn blit_nine_slice_scaled(
+        &mut self,
+        sprite: &Bitmap,
+        sub_rect: Rect,
+        insets: Insets,
+        rect: Rect,
+    ) {
+        self.blit_nine_slice_with(sprite, sub_rect, insets, rect, Canvas::blit_scaled);
+    }
+
+    /// Draw the 9 parts of a section of a sprite with a function which fills a rectangle with a
+    /// part.
+    fn blit_nine_slice_with<F>(
+        &mut self,
+        sprite: &Bitmap,
+        sub_rect: Rect,
+        insets: Insets,
+        rect: Rect,
+        blit_part: F,
+    ) where
+        F: Fn(&mut Self, &Bitmap, Rect, Rect),
+    {
+        if rect.is_empty() || rect.intersection(self.bounds()).is_empty() {
+            return;
+        }
+
+        let columns = nine_slice_spans(
+            (sub_rect.x, sub_rect.width),
+            (insets.left, insets.right),
+            (rect.x, rect.width),
+        );
+        let rows = nine_slice_spans(
+            (sub_rect.y, sub_rect.height),
+            (insets.top, insets.bottom),
+            (rect.y, rect.height),
+        );
+
+        for &((src_y, src_height), (dst_y, dst_height)) in rows.iter() {
+            for &((src_x, src_width), (dst_x, dst_width)) in columns.iter() {
+                blit_part(
+                    self,
+                    sprite,
+                    Rect::new(src_x, src_y, src_width, src_height),
+                    Rect::new(dst_x, dst_y, dst_width, dst_height),
+                );
+            }
+        }
+    }
+
+    /// Draw a section of a sprite with every pixel changed by a function before it's blended.
+    fn blit_with<F>(&mut self, sprite: &Bitmap, pos: (i32, i32), sub_rect: Rect, pixel_fn: F)
+    where
+        F: Fn(u32) -> u32,
+    {
+        // Don't read outside of the sprite
+        let src = sub_rect.intersection(Rect::from_pos_size((0, 0), sprite.size()));
+        let dst = Rect::from_pos_size(
+            (
+                pos.0 + src.x - sub_rect.x + self.offset.0,
+                pos.1 + src.y - sub_rect.y + self.offset.1,
+            ),
+            src.size(),
+        );
+
+        // Don't draw outside of the region
+        let visible = dst.intersection(self.clip);
+        if visible.is_empty() {
+            return;
+        }
+
+        let pixels = sprite.pixels();
+        for y in visible.y..visible.bottom() {
+            let src_row = (src.y + y - dst.y) * sprite.width() + src.x - dst.x;
+            let row = y as usize * self.stride;
+            for x in visible.x..visible.right() {
+                let pixel = pixels[(src_row + x) as usize];
+                let alpha = pixel >> 24;
+                if alpha == 0 {
+                    continue;
+                }
+
+                let index = row + x as usize;
+                let below = self.buffer.get(index);
+                self.buffer.set(index, blend(below, pixel_fn(pixel), alpha));
+            }
+        }
+    }
+
+    /// Fill a rectangle by repeating a section of a sprite, every pixel is changed by a function.
+    fn blit_tiled<F>(&mut self, sprite: &Bitmap, sub_rect: Rect, rect: Rect, pixel_fn: F)
+    where
+        F: Fn(u32) -> u32,
+    {
+        if sub_rect.is_empty() {
+            return;
+        }
+
+        let visible = rect.intersection(self.bounds());
+        if visible.is_empty() {
+            return;
+        }
+
+        // Skip the tiles before the visible part
+        let mut y = rect.y + (visible.y - rect.y) / sub_rect.height * sub_rect.height;
+        while y < visible.bottom() {
+            let mut x = rect.x + (visible.x - rect.x) / sub_rect.width * sub_rect.width;
+            while x < visible.right() {
+                let width = sub_rect.width.min(rect.right() - x);
+                let height = sub_rect.height.min(rect.bottom() - y);
+                self.blit_with(
+                    sprite,
+                    (x, y),
+                    Rect::new(sub_rect.x, sub_rect.y, width, height),
+                    &pixel_fn,
+                );
+
+                x += sub_rect.width;
+            }
+
+            y += sub_rect.height;
+        }
+    }
+
+    /// Fill a rectangle by scaling a section of a sprite to it, every pixel is drawn with the
+    /// nearest pixel of the section.
+    fn blit_scaled(&mut self, sprite: &Bitmap, sub_rect: Rect, rect: Rect) {
+        // Don't read outside of the sprite
+        let src = sub_rect.intersection(Rect::from_pos_size((0, 0), sprite.size()));
+        if src.is_empty() {
+            return;
+        }
+
+        let dst = rect.translate(self.offset.0, self.offset.1);
+        let visible = dst.intersection(self.clip);
+        if visible.is_empty() {
+            return;
+        }
+
+        let pixels = sprite.pixels();
+        for y in visible.y..visible.bottom() {
+            let src_y = src.y + (y - dst.y) * src.height / dst.height;
+            let row = y as usize * self.stride;
+            for x in visible.x..visible.right() {
+                let src_x = src.x + (x - dst.x) * src.width / dst.width;
+                let pixel = pixels[(src_x + src_y * sprite.width()) as usize];
+                let alpha = pixel >> 24;
+                if alpha == 0 {
+                    continue;
+                }
+
+                let index = row + x as usize;
+                let below = self.buffer.get(index);
+                self.buffer.set(index, blend(below, pixel, alpha));
+            }
+        }
+    }
+}
+
+/// Draw a color over a pixel with an alpha from `0` to `0xFF`, the alpha of the color is ignored.
+///
+/// The blended pixel is opaque.
+fn blend(pixel: u32, color: u32, alpha: u32) -> u32 {
+    match alpha {
+        0 => pixel,
+        0xFF => color | 0xFF_00_00_00,
+        _ => {
+            let channel = |shift: u32| {
+                let (src, dst) = ((color >> shift) & 0xFF, (pixel >> shift) & 0xFF);
+
+                ((src * alpha + dst * (0xFF - alpha)) / 0xFF) << shift
+            };
+
+            0xFF_00_00_00 | channel(16) | channel(8) | channel(0)
+        }
+    }
+}
+
+/// Multiply the color channels of two colors.
+fn multiply(a: u32, b: u32) -> u32 {
+    let channel = |shift: u32| (((a >> shift) & 0xFF) * ((b >> shift) & 0xFF) / 0xFF) << shift;
+
+    (a & 0xFF_00_00_00) | channel(16) | channel(8) | channel(0)
+}
+
+/// Split a source and destination span into the start border, the middle and the end border.
+///
+/// Each span is a start and a length.
+fn nine_slice_spans(
+    src: (i32, i32),
+    (start_inset, end_inset): (i32, i32),
+    dst: (i32, i32),
+) -> [((i32, i32), (i32, i32)); 3] {
+    // Shrink the borders when they don't fit
+    let start_inset = start_inset.max(0).min(src.1).min(dst.1 / 2 + dst.1 % 2);
+    let end_inset = end_inset
+        .max(0)
+        .min(src.1 - start_inset)
+        .min(dst.1 - start_inset);
+
+    [
+        ((src.0, start_inset), (dst.0, start_inset)),
+        (
+            (src.0 + start_inset, src.1 - start_inset - end_inset),
+            (dst.0 + start_inset, dst.1 - start_inset - end_inset),
+        ),
+        (
+            (src.0 + src.1 - end_inset, end_inset),
+            (dst.0 + dst.1 - end_inset, end_inset),
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A sprite with a border of 1 pixel and a horizontal gradient in between.
+    fn gradient() -> Bitmap {
+        let pixels = [0xFF_00_00_00, 0xFF_40_40_40, 0xFF_80_80_80, 0xFF_FF_FF_FF];
+
+        Bitmap::from_argb(&pixels, 4)
+    }
+
+    #[test]
+    fn nine_slice_scaled_without_seams() {
+        let mut buffer = vec![0u32; 10];
+        let mut canvas = Canvas::new(&mut buffer, 10, 1);
+        canvas.blit_nine_slice_scaled(
+            &gradient(),
+            Rect::new(0, 0, 4, 1),
+            Insets::new(1, 0, 1, 0),
+            Rect::new(0, 0, 10, 1),
+        );
+
+        // The corners stay intact and the center only gets brighter
+        assert_eq!(buffer[0], 0xFF_00_00_00);
+        assert_eq!(buffer[9], 0xFF_FF_FF_FF);
+        assert!(buffer.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert_eq!(
+            &buffer[1..9],
+            &[
+                0xFF_40_40_40,
+                0xFF_40_40_40,
+                0xFF_40_40_40,
+                0xFF_40_40_40,
+                0xFF_80_80_80,
+                0xFF_80_80_80,
+                0xFF_80_80_80,
+                0xFF_80_80_80
+            ]
+        );
+    }
+
+    #[test]
+    fn nine_slice_repeated() {
+        let mut buffer = vec![0u32; 6];
+        let mut canvas = Canvas::new(&mut buffer, 6, 1);
+        canvas.blit_nine_slice(
+            &gradient(),
+            Rect::new(0, 0, 4, 1),
+            Insets::new(1, 0, 1, 0),
+            Rect::new(0, 0, 6, 1),
+        );
+
+        assert_eq!(
+            buffer,
+            vec![
+                0xFF_00_00_00,
+                0xFF_40_40_40,
+                0xFF_80_80_80,
+                0xFF_40_40_40,
+                0xFF_80_80_80,
+                0xFF_FF_FF_FF
+            ]
+        );
+    }
+
+    #[test]
+    fn nine_slice_tinted() {
+        let mut buffer = vec![0u32; 6];
+        let mut canvas = Canvas::new(&mut buffer, 6, 1);
+        canvas.blit_nine_slice_tinted(
+            &gradient(),
+            Rect::new(0, 0, 4, 1),
+            Insets::new(1, 0, 1, 0),
+            Rect::new(0, 0, 6, 1),
+            Color::from_u32(0x80_80_80),
+        );
+
+        // Every part is darkened, not only the center
+        assert_eq!(
+            buffer,
+            vec![
+                0xFF_00_00_00,
+                0xFF_20_20_20,
+                0xFF_40_40_40,
+                0xFF_20_20_20,
+                0xFF_40_40_40,
+                0xFF_80_80_80
+            ]
+        );
+    }
+}
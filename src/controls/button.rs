@@ -2,6 +2,12 @@ use blit::Color;
 
 use super::*;
 
+/// The space between the border of a button and the caption.
+const CAPTION_PADDING: i32 = 4;
+
+/// What sprites are multiplied with when they are disabled and have no part for it.
+pub(super) const DISABLED_TINT: u32 = 0x80_80_80;
+
 /// The skin of the button is rendered by an algorithm.
 #[derive(Debug)]
 pub struct Flat {
@@ -15,6 +21,14 @@ pub struct Image {
     pub sprite_ref: SpriteRef,
 }
 
+/// The skin of the button is a nine-slice sprite, the edges and the center are repeated to fill a
+/// size.
+#[derive(Debug)]
+pub struct Sliced {
+    pub nine_slice_ref: NineSliceRef,
+    pub size: (i32, i32),
+}
+
 /// In what state the button currently is in as determined by the `update` function.
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum ButtonState {
@@ -24,22 +38,149 @@ pub enum ButtonState {
     Hover,
     /// The mouse is hovering over the button and it's pressed.
     Pressed,
+    /// The button is disabled with `Gui::set_enabled` and ignores the mouse.
+    Disabled,
+}
+
+/// Keeps track of the mouse interacting with a clickable area.
+#[derive(Debug)]
+pub(super) struct Clickable {
+    pub state: ButtonState,
+    /// Whether the left mouse button went down while hovering over the area, a click only
+    /// happens when it's also released above it.
+    armed: bool,
+}
+
+impl Clickable {
+    pub fn new() -> Self {
+        Clickable {
+            state: ButtonState::Normal,
+            armed: false,
+        }
+    }
+
+    /// Determine the new state, returns true when the area has been clicked.
+    pub fn update(&mut self, args: &ControlState, pos: (i32, i32), size: (i32, i32)) -> bool {
+        let hovering = args.mouse_collision(pos, size);
+        if !hovering {
+            self.state = ButtonState::Normal;
+        } else {
+            self.state = if args.mouse_down {
+                ButtonState::Pressed
+            } else {
+                ButtonState::Hover
+            };
+        }
+
+        // Only a press that starts above the area can result in a click
+        if hovering && args.mouse_just_pressed(MouseButton::Left) {
+            self.armed = true;
+        }
+        let clicked = self.armed && hovering && args.mouse_just_released(MouseButton::Left);
+        if !args.mouse_down {
+            self.armed = false;
+        }
+
+        clicked
+    }
+
+    /// Go to the disabled state or back to the normal state.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.state = if enabled {
+            ButtonState::Normal
+        } else {
+            ButtonState::Disabled
+        };
+        self.armed = false;
+    }
+}
+
+/// A text drawn on top of a button.
+#[derive(Debug)]
+struct Caption {
+    font_ref: FontRef,
+    text: String,
+    align: Alignment,
+    /// The tint of the text for the normal, hover, pressed and disabled state, overriding the
+    /// text color of the theme.
+    colors: [Option<Color>; 4],
 }
 
+impl Caption {
+    /// Draw the text vertically centered inside the area.
+    fn draw(&self, canvas: &mut Canvas, res: &Resources, rect: Rect, state: ButtonState) {
+        let font = res
+            .get_themed_font(ControlType::Button, self.font_ref)
+            .unwrap();
+        let text_size = font.text_size(&self.text);
+
+        let x = match self.align {
+            Alignment::Start => CAPTION_PADDING,
+            Alignment::Center => Alignment::Center.offset(rect.width, text_size.0),
+            Alignment::End => rect.width - text_size.0 - CAPTION_PADDING,
+        };
+        let pos = (
+            rect.x + x,
+            rect.y + Alignment::Center.offset(rect.height, text_size.1),
+        );
+
+        let color = self.colors[state as usize].or_else(|| {
+            res.theme()
+                .style(ControlType::Button, state.into())
+                .text_color
+        });
+        match color {
+            Some(color) => font.draw_string_tinted(canvas, self.text.as_str(), pos, color),
+            None => font.draw_string(canvas, self.text.as_str(), pos),
+        }
+    }
+}
+
+/// A closure called when the state of a button changes.
+pub type StateCallback<S> = Box<dyn FnMut(&mut Button<S>, ButtonState)>;
+
+/// A closure called when an event happens on a button.
+pub type ButtonCallback<S> = Box<dyn FnMut(&mut Button<S>)>;
+
 /// A button widget that can be rendered in multiple ways:
 /// `Flat`: using a simpel pixel rectangle algorithm.
-/// `Image`: using a spritesheet divided into 3 parts for rendering its state.
+/// `Image`: using a spritesheet divided into 3 or 4 parts for rendering its state.
+/// `Sliced`: using a nine-slice spritesheet divided into 3 or 4 parts stretched to any size.
 pub struct Button<S> {
-    /// How the button is rendered (`Flat` or `Image`).
+    /// How the button is rendered (`Flat`, `Image` or `Sliced`).
     show: S,
 
     pos: (i32, i32),
-    state: ButtonState,
-
-    state_changed: fn(&mut Button<S>, ButtonState),
+    mouse: Clickable,
+
+    caption: Option<Caption>,
+    /// Only used by `Flat` buttons.
+    auto_size: bool,
+    /// Only used by `Image` and `Sliced` buttons.
+    disabled_part: bool,
+
+    state_changed: Option<StateCallback<S>>,
+    clicked: Option<ButtonCallback<S>>,
+    hover_entered: Option<ButtonCallback<S>>,
+    hover_left: Option<ButtonCallback<S>>,
 }
 
 impl<S> Button<S> {
+    fn with_show(show: S) -> Self {
+        Button {
+            show,
+            pos: (0, 0),
+            mouse: Clickable::new(),
+            caption: None,
+            auto_size: false,
+            disabled_part: false,
+            state_changed: None,
+            clicked: None,
+            hover_entered: None,
+            hover_left: None,
+        }
+    }
+
     /// Retrieve the position.
     pub fn pos(&self) -> (i32, i32) {
         self.pos
@@ -59,86 +200,306 @@ impl<S> Button<S> {
 
     /// Get if the button is currently pressed.
     pub fn pressed(&self) -> bool {
-        match self.state {
-            ButtonState::Pressed => true,
-            _ => false,
+        self.mouse.state == ButtonState::Pressed
+    }
+
+    /// Get if the button reacts to the mouse, change it with `Gui::set_enabled`.
+    pub fn enabled(&self) -> bool {
+        self.mouse.state != ButtonState::Disabled
+    }
+
+    /// Map a caption which is drawn centered on the button.
+    pub fn with_text(mut self, font_ref: FontRef, text: &str) -> Self {
+        self.caption = Some(Caption {
+            font_ref,
+            text: String::from(text),
+            align: Alignment::Center,
+            colors: [None; 4],
+        });
+
+        self
+    }
+
+    /// Map where the caption is placed horizontally, by default it's centered.
+    pub fn with_text_align(mut self, align: Alignment) -> Self {
+        if let Some(caption) = self.caption.as_mut() {
+            caption.align = align;
+        }
+
+        self
+    }
+
+    /// Map the color the caption is tinted with in a state instead of the text color of the
+    /// theme, the colors of the font are multiplied by it so a white font results in text with
+    /// exactly this color.
+    pub fn with_text_color(mut self, state: ButtonState, color: Color) -> Self {
+        if let Some(caption) = self.caption.as_mut() {
+            caption.colors[state as usize] = Some(color);
+        }
+
+        self
+    }
+
+    /// Get the caption, empty if there is none.
+    pub fn text(&self) -> &str {
+        self.caption
+            .as_ref()
+            .map_or("", |caption| caption.text.as_str())
+    }
+
+    /// Change the caption, it's only drawn when a font is mapped with `with_text`.
+    pub fn set_text(&mut self, text: &str) {
+        if let Some(caption) = self.caption.as_mut() {
+            caption.text = String::from(text);
         }
     }
 
     /// Set the event to a closure which will be called if the button state changes.
-    pub fn with_callback(mut self, func: fn(&mut Button<S>, ButtonState)) -> Self {
-        self.state_changed = func;
+    pub fn with_callback<F>(mut self, func: F) -> Self
+    where
+        F: FnMut(&mut Button<S>, ButtonState) + 'static,
+    {
+        self.state_changed = Some(Box::new(func));
 
         self
     }
 
-    /// An empty function so the callback doesn't has to be bound.
-    fn empty_state_changed_callback(_: &mut Button<S>, _: ButtonState) {}
-}
+    /// Set the event to a closure which will be called when the mouse is pressed and released
+    /// on the button.
+    pub fn with_click_callback<F>(mut self, func: F) -> Self
+    where
+        F: FnMut(&mut Button<S>) + 'static,
+    {
+        self.clicked = Some(Box::new(func));
 
-impl Button<Flat> {
-    /// Create a new colored button without text.
-    pub fn new(size: (i32, i32), color: Color) -> Self {
-        let show = Flat { size, color };
+        self
+    }
 
-        Button {
-            show,
-            pos: (0, 0),
-            state: ButtonState::Normal,
-            state_changed: Button::empty_state_changed_callback,
+    /// Set the event to a closure which will be called when the mouse starts hovering over the
+    /// button.
+    pub fn with_hover_enter_callback<F>(mut self, func: F) -> Self
+    where
+        F: FnMut(&mut Button<S>) + 'static,
+    {
+        self.hover_entered = Some(Box::new(func));
+
+        self
+    }
+
+    /// Set the event to a closure which will be called when the mouse stops hovering over the
+    /// button.
+    pub fn with_hover_leave_callback<F>(mut self, func: F) -> Self
+    where
+        F: FnMut(&mut Button<S>) + 'static,
+    {
+        self.hover_left = Some(Box::new(func));
+
+        self
+    }
+
+    /// Determine the new state of the button and call the callbacks of what changed.
+    fn update_state(
+        &mut self,
+        args: &ControlState,
+        size: (i32, i32),
+        events: &mut Vec<GuiEventKind>,
+    ) {
+        let prev_state = self.mouse.state;
+        let clicked = self.mouse.update(args, self.pos, size);
+        let state = self.mouse.state;
+
+        if prev_state == ButtonState::Normal && state != ButtonState::Normal {
+            events.push(GuiEventKind::HoverEntered);
+            self.call(|b| &mut b.hover_entered);
+        }
+
+        if prev_state != state {
+            if let Some(mut func) = self.state_changed.take() {
+                func(self, state);
+                self.state_changed.get_or_insert(func);
+            }
+        }
+
+        if clicked {
+            self.click(events);
+        }
+
+        if prev_state != ButtonState::Normal && state == ButtonState::Normal {
+            events.push(GuiEventKind::HoverLeft);
+            self.call(|b| &mut b.hover_left);
         }
     }
-}
 
-impl Control for Button<Flat> {
-    fn update(&mut self, args: &ControlState, _res: &Resources) {
-        let prev_state = self.state;
+    /// Emit a click and call the callback for it.
+    fn click(&mut self, events: &mut Vec<GuiEventKind>) {
+        events.push(GuiEventKind::Clicked);
+        self.call(|b| &mut b.clicked);
+    }
 
-        if !args.mouse_collision(self.pos, self.show.size) {
-            self.state = ButtonState::Normal;
+    /// Enable or disable the button and call the state callback when it changed.
+    fn change_enabled(&mut self, enabled: bool) {
+        let prev_state = self.mouse.state;
+        self.mouse.set_enabled(enabled);
+        let state = self.mouse.state;
+
+        if prev_state != state {
+            if let Some(mut func) = self.state_changed.take() {
+                func(self, state);
+                self.state_changed.get_or_insert(func);
+            }
+        }
+    }
+
+    /// Which of the parts of a sprite sheet is drawn, a disabled button without a part for it
+    /// uses the normal part.
+    fn sheet_part(&self) -> i32 {
+        match self.mouse.state {
+            ButtonState::Normal => 0,
+            ButtonState::Hover => 1,
+            ButtonState::Pressed => 2,
+            ButtonState::Disabled if self.disabled_part => 3,
+            ButtonState::Disabled => 0,
+        }
+    }
+
+    /// The amount of parts the sprite sheet is divided into.
+    fn sheet_parts(&self) -> i32 {
+        if self.disabled_part {
+            4
         } else {
-            self.state = if args.mouse_down {
-                ButtonState::Pressed
-            } else {
-                ButtonState::Hover
-            };
+            3
         }
+    }
 
-        if prev_state != self.state {
-            let state = self.state;
-            (self.state_changed)(self, state);
+    /// The tint of a sprite sheet part that's not meant for the current state.
+    fn sheet_tint(&self) -> Option<Color> {
+        if self.mouse.state == ButtonState::Disabled && !self.disabled_part {
+            Some(Color::from_u32(DISABLED_TINT))
+        } else {
+            None
         }
     }
 
-    fn draw(&self, buffer: &mut Vec<u32>, buffer_width: usize, _res: &Resources) {
-        let mut color = self.show.color.u32();
+    /// The size needed to fit the caption with padding around it.
+    fn caption_size(&self, res: &Resources) -> Option<(i32, i32)> {
+        self.caption.as_ref().map(|caption| {
+            let text_size = res
+                .get_themed_font(ControlType::Button, caption.font_ref)
+                .unwrap()
+                .text_size(&caption.text);
+
+            (
+                text_size.0 + CAPTION_PADDING * 2,
+                text_size.1 + CAPTION_PADDING * 2,
+            )
+        })
+    }
 
-        match self.state {
-            ButtonState::Hover => color |= 0x44_44_44,
-            ButtonState::Pressed => color &= 0xAA_AA_AA,
-            _ => (),
+    /// Draw the caption on top of the skin.
+    fn draw_caption(&self, canvas: &mut Canvas, res: &Resources, size: (i32, i32)) {
+        if let Some(caption) = self.caption.as_ref() {
+            caption.draw(
+                canvas,
+                res,
+                Rect::from_pos_size(self.pos, size),
+                self.mouse.state,
+            );
         }
+    }
 
-        for y in self.pos.1..self.pos.1 + self.show.size.1 {
-            for x in self.pos.0..self.pos.0 + self.show.size.0 {
-                let index = x as usize + y as usize * buffer_width;
-                if x == self.pos.0
-                    || x == self.pos.0 + self.show.size.0 - 1
-                    || y == self.pos.1
-                    || y == self.pos.1 + self.show.size.1 - 1
-                {
-                    buffer[index] = 0;
-                } else {
-                    buffer[x as usize + y as usize * buffer_width] = color;
-                }
-            }
+    /// Call one of the callbacks with the button as the argument.
+    fn call(&mut self, callback: fn(&mut Self) -> &mut Option<ButtonCallback<S>>) {
+        if let Some(mut func) = callback(self).take() {
+            func(self);
+
+            // Only put it back when the callback didn't replace itself
+            callback(self).get_or_insert(func);
         }
     }
+}
+
+impl Button<Flat> {
+    /// Create a new colored button without text, use `with_text` to add a caption.
+    pub fn new(size: (i32, i32), color: Color) -> Self {
+        Button::with_show(Flat { size, color })
+    }
+
+    /// Create a new colored button with a caption, the button is sized to fit the text.
+    pub fn new_with_text(font_ref: FontRef, text: &str, color: Color) -> Self {
+        Button::new((0, 0), color)
+            .with_text(font_ref, text)
+            .with_auto_size()
+    }
+
+    /// Size the button to fit the caption, when the caption changes the button is resized.
+    pub fn with_auto_size(mut self) -> Self {
+        self.auto_size = true;
+
+        self
+    }
+
+    /// The size of the button, which depends on the caption when it's automatically sized.
+    fn flat_size(&self, res: &Resources) -> (i32, i32) {
+        match self.caption_size(res) {
+            Some(size) if self.auto_size => size,
+            _ => self.show.size,
+        }
+    }
+}
+
+impl Control for Button<Flat> {
+    fn update(&mut self, args: &ControlState, res: &Resources, events: &mut Vec<GuiEventKind>) {
+        let size = self.size(res);
+        self.update_state(args, size, events);
+    }
+
+    fn draw(&self, canvas: &mut Canvas, res: &Resources) {
+        let size = self.size(res);
+
+        res.theme()
+            .style(ControlType::Button, self.mouse.state.into())
+            .draw_rect(
+                canvas,
+                res,
+                Rect::from_pos_size(self.pos, size),
+                self.show.color,
+            );
+
+        self.draw_caption(canvas, res, size);
+    }
 
     fn control_type(&self) -> ControlType {
         ControlType::Button
     }
 
+    fn pos(&self) -> (i32, i32) {
+        self.pos
+    }
+
+    fn set_pos(&mut self, x: i32, y: i32) {
+        self.pos = (x, y);
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.change_enabled(enabled);
+    }
+
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    fn activate(&mut self, events: &mut Vec<GuiEventKind>) {
+        self.click(events);
+    }
+
+    fn size(&self, res: &Resources) -> (i32, i32) {
+        self.flat_size(res)
+    }
+
+    fn preferred_size(&self, res: &Resources) -> (i32, i32) {
+        self.caption_size(res).unwrap_or(self.show.size)
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
@@ -146,10 +507,14 @@ impl Control for Button<Flat> {
     fn as_any_mut(&mut self) -> &mut dyn Any {
         self
     }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
 }
 
 impl Button<Image> {
-    /// Create a new button with a sprite without text.
+    /// Create a new button with a sprite without text, use `with_text` to add a caption.
     ///
     /// The button image needs to be 3 buttons divided vertically:
     ///  1. normal state
@@ -165,66 +530,156 @@ impl Button<Image> {
     /// |Pressed|
     /// +-------+
     /// ```
+    ///
+    /// When the button is disabled the normal state is drawn darkened, unless the image has a
+    /// fourth part for it, see `with_disabled_part`.
     pub fn new_with_sprite(sprite_ref: SpriteRef) -> Self {
-        let img = Image { sprite_ref };
+        Button::with_show(Image { sprite_ref })
+    }
 
-        Button {
-            show: img,
-            pos: (0, 0),
-            state: ButtonState::Normal,
-            state_changed: Button::empty_state_changed_callback,
-        }
+    /// The image has a fourth button below the others which is drawn when it's disabled.
+    pub fn with_disabled_part(mut self) -> Self {
+        self.disabled_part = true;
+
+        self
     }
 }
 
 impl Control for Button<Image> {
-    fn update(&mut self, args: &ControlState, res: &Resources) {
-        let prev_state = self.state;
+    fn update(&mut self, args: &ControlState, res: &Resources, events: &mut Vec<GuiEventKind>) {
+        let size = self.size(res);
+        self.update_state(args, size, events);
+    }
 
+    fn draw(&self, canvas: &mut Canvas, res: &Resources) {
         let sprite = res.get_sprite(self.show.sprite_ref).unwrap();
+        let draw_size = self.size(res);
 
-        let mut real_size = sprite.size();
-        real_size.1 /= 3;
-        if !args.mouse_collision(self.pos, real_size) {
-            self.state = ButtonState::Normal;
-        } else {
-            self.state = if args.mouse_down {
-                ButtonState::Pressed
-            } else {
-                ButtonState::Hover
-            };
+        let sub_rect = Rect::new(0, draw_size.1 * self.sheet_part(), draw_size.0, draw_size.1);
+        match self.sheet_tint() {
+            Some(tint) => canvas.blit_tinted(sprite, self.pos, sub_rect, tint),
+            None => canvas.blit(sprite, self.pos, sub_rect),
         }
 
-        if prev_state != self.state {
-            let state = self.state;
-            (self.state_changed)(self, state);
-        }
+        self.draw_caption(canvas, res, draw_size);
     }
 
-    fn draw(&self, buffer: &mut Vec<u32>, buffer_width: usize, res: &Resources) {
-        let sprite = res.get_sprite(self.show.sprite_ref).unwrap();
+    fn control_type(&self) -> ControlType {
+        ControlType::Button
+    }
 
-        let mut draw_size = sprite.size();
-        draw_size.1 /= 3;
+    fn pos(&self) -> (i32, i32) {
+        self.pos
+    }
 
-        let height_offset = match self.state {
-            ButtonState::Normal => 0,
-            ButtonState::Hover => draw_size.1,
-            ButtonState::Pressed => draw_size.1 * 2,
-        } as i32;
-
-        sprite.blit_rect(
-            buffer,
-            buffer_width,
-            self.pos,
-            (0, height_offset, draw_size.0, draw_size.1),
-        );
+    fn set_pos(&mut self, x: i32, y: i32) {
+        self.pos = (x, y);
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.change_enabled(enabled);
+    }
+
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    fn activate(&mut self, events: &mut Vec<GuiEventKind>) {
+        self.click(events);
+    }
+
+    fn size(&self, res: &Resources) -> (i32, i32) {
+        let size = res.get_sprite(self.show.sprite_ref).unwrap().size();
+
+        (size.0, size.1 / self.sheet_parts())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
+impl Button<Sliced> {
+    /// Create a new button with a nine-slice sprite without text, use `with_text` to add a
+    /// caption.
+    ///
+    /// The nine-slice image needs to be divided vertically into 3 parts just like
+    /// `Button::new_with_sprite`, the insets apply to every part.
+    pub fn new_with_nine_slice(nine_slice_ref: NineSliceRef, size: (i32, i32)) -> Self {
+        Button::with_show(Sliced {
+            nine_slice_ref,
+            size,
+        })
+    }
+
+    /// The nine-slice image has a fourth part below the others which is drawn when it's
+    /// disabled, without it the normal part is drawn darkened.
+    pub fn with_disabled_part(mut self) -> Self {
+        self.disabled_part = true;
+
+        self
+    }
+}
+
+impl Control for Button<Sliced> {
+    fn update(&mut self, args: &ControlState, _res: &Resources, events: &mut Vec<GuiEventKind>) {
+        let size = self.show.size;
+        self.update_state(args, size, events);
+    }
+
+    fn draw(&self, canvas: &mut Canvas, res: &Resources) {
+        let nine_slice = res.get_nine_slice(self.show.nine_slice_ref).unwrap();
+
+        let rect = Rect::from_pos_size(self.pos, self.show.size);
+        let (row, rows) = (self.sheet_part(), self.sheet_parts());
+        match self.sheet_tint() {
+            Some(tint) => nine_slice.draw_row_tinted(canvas, row, rows, rect, tint),
+            None => nine_slice.draw_row(canvas, row, rows, rect),
+        }
+
+        self.draw_caption(canvas, res, self.show.size);
     }
 
     fn control_type(&self) -> ControlType {
         ControlType::Button
     }
 
+    fn pos(&self) -> (i32, i32) {
+        self.pos
+    }
+
+    fn set_pos(&mut self, x: i32, y: i32) {
+        self.pos = (x, y);
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.change_enabled(enabled);
+    }
+
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    fn activate(&mut self, events: &mut Vec<GuiEventKind>) {
+        self.click(events);
+    }
+
+    fn size(&self, _res: &Resources) -> (i32, i32) {
+        self.show.size
+    }
+
+    fn preferred_size(&self, res: &Resources) -> (i32, i32) {
+        self.caption_size(res).unwrap_or(self.show.size)
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
@@ -232,4 +687,8 @@ impl Control for Button<Image> {
     fn as_any_mut(&mut self) -> &mut dyn Any {
         self
     }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
 }
@@ -0,0 +1,718 @@
+use blit::Color;
+
+use super::button::{Clickable, DISABLED_TINT};
+use super::*;
+
+/// The space between the box and the label.
+const LABEL_SPACING: i32 = 4;
+
+/// The space between the options of a radio group.
+const OPTION_SPACING: i32 = 4;
+
+/// Draw the box of a flat checkbox or radio button with the style of the control type.
+fn draw_flat_box(
+    canvas: &mut Canvas,
+    res: &Resources,
+    control_type: ControlType,
+    show: &Flat,
+    pos: (i32, i32),
+    state: ButtonState,
+    checked: bool,
+) {
+    let style = res.theme().style(control_type, state.into());
+    style.draw_rect(canvas, res, Rect::from_pos_size(pos, show.size), show.color);
+
+    // The check mark has the color of the border
+    if checked {
+        let inset = (show.size.0.min(show.size.1) / 4).max(2);
+        canvas.fill_rect(
+            Rect::new(
+                pos.0 + inset,
+                pos.1 + inset,
+                show.size.0 - inset * 2,
+                show.size.1 - inset * 2,
+            ),
+            style.border_color,
+        );
+    }
+}
+
+/// The size of a single box in the sprite sheet of a checkbox or radio button.
+fn sprite_box_size(show: &Image, res: &Resources) -> (i32, i32) {
+    let size = res.get_sprite(show.sprite_ref).unwrap().size();
+
+    (size.0 / 2, size.1 / 3)
+}
+
+/// Draw the box of a checkbox or radio button from a sprite sheet.
+fn draw_sprite_box(
+    canvas: &mut Canvas,
+    res: &Resources,
+    show: &Image,
+    pos: (i32, i32),
+    state: ButtonState,
+    checked: bool,
+) {
+    let sprite = res.get_sprite(show.sprite_ref).unwrap();
+    let size = sprite_box_size(show, res);
+
+    let x = if checked { size.0 } else { 0 };
+    let y = match state {
+        ButtonState::Normal | ButtonState::Disabled => 0,
+        ButtonState::Hover => size.1,
+        ButtonState::Pressed => size.1 * 2,
+    };
+
+    let sub_rect = Rect::new(x, y, size.0, size.1);
+    if state == ButtonState::Disabled {
+        // There's no part for the disabled state so the normal state is darkened
+        canvas.blit_tinted(sprite, pos, sub_rect, Color::from_u32(DISABLED_TINT));
+    } else {
+        canvas.blit(sprite, pos, sub_rect);
+    }
+}
+
+/// The area of a box with a label right of it, and the position of the label.
+fn labeled_box(
+    res: &Resources,
+    control_type: ControlType,
+    pos: (i32, i32),
+    box_size: (i32, i32),
+    font_ref: Option<FontRef>,
+    text: &str,
+) -> (Rect, (i32, i32)) {
+    let text_size = match font_ref {
+        Some(font_ref) if !text.is_empty() => res
+            .get_themed_font(control_type, font_ref)
+            .unwrap()
+            .text_size(text),
+        _ => return (Rect::from_pos_size(pos, box_size), pos),
+    };
+
+    let height = box_size.1.max(text_size.1);
+    let label_pos = (
+        pos.0 + box_size.0 + LABEL_SPACING,
+        pos.1 + (height - text_size.1) / 2,
+    );
+
+    (
+        Rect::new(
+            pos.0,
+            pos.1,
+            box_size.0 + LABEL_SPACING + text_size.0,
+            height,
+        ),
+        label_pos,
+    )
+}
+
+/// A box which can be checked and unchecked by clicking on it or its label.
+///
+/// It can be rendered in multiple ways:
+/// `Flat`: using a simpel pixel rectangle algorithm.
+/// `Image`: using a spritesheet divided into 2 columns and 3 rows for rendering its state.
+///
+/// It emits `GuiEventKind::Toggled` when it's clicked.
+pub struct Checkbox<S> {
+    /// How the box is rendered (`Flat` or `Image`).
+    show: S,
+
+    pos: (i32, i32),
+    mouse: Clickable,
+    checked: bool,
+
+    font_ref: Option<FontRef>,
+    text: String,
+}
+
+impl<S> Checkbox<S> {
+    fn with_show(show: S) -> Self {
+        Checkbox {
+            show,
+            pos: (0, 0),
+            mouse: Clickable::new(),
+            checked: false,
+            font_ref: None,
+            text: String::new(),
+        }
+    }
+
+    /// Retrieve the position.
+    pub fn pos(&self) -> (i32, i32) {
+        self.pos
+    }
+
+    /// Map a position.
+    pub fn with_pos(mut self, x: i32, y: i32) -> Self {
+        self.pos = (x, y);
+
+        self
+    }
+
+    /// Change the position.
+    pub fn set_pos(&mut self, x: i32, y: i32) {
+        self.pos = (x, y);
+    }
+
+    /// Get if the box is checked.
+    pub fn checked(&self) -> bool {
+        self.checked
+    }
+
+    /// Map if the box is checked.
+    pub fn with_checked(mut self, checked: bool) -> Self {
+        self.checked = checked;
+
+        self
+    }
+
+    /// Check or uncheck the box.
+    pub fn set_checked(&mut self, checked: bool) {
+        self.checked = checked;
+    }
+
+    /// Map a label which is drawn right of the box.
+    pub fn with_label(mut self, font_ref: FontRef, text: &str) -> Self {
+        self.font_ref = Some(font_ref);
+        self.text = String::from(text);
+
+        self
+    }
+
+    /// Update the text of the label.
+    pub fn set_text(&mut self, text: &str) {
+        self.text = String::from(text);
+    }
+
+    /// Toggle the box when it's clicked.
+    fn update_state(
+        &mut self,
+        args: &ControlState,
+        res: &Resources,
+        box_size: (i32, i32),
+        events: &mut Vec<GuiEventKind>,
+    ) {
+        let area = self.area(res, box_size);
+
+        if self.mouse.update(args, area.pos(), area.size()) {
+            self.toggle(events);
+        }
+    }
+
+    /// Check or uncheck the box and emit it.
+    fn toggle(&mut self, events: &mut Vec<GuiEventKind>) {
+        self.checked = !self.checked;
+        events.push(GuiEventKind::Toggled(self.checked));
+    }
+
+    /// The area of the box together with the label.
+    fn area(&self, res: &Resources, box_size: (i32, i32)) -> Rect {
+        self.labeled_box(res, box_size).0
+    }
+
+    /// The area of the box together with the label and the position of the label.
+    fn labeled_box(&self, res: &Resources, box_size: (i32, i32)) -> (Rect, (i32, i32)) {
+        labeled_box(
+            res,
+            ControlType::Checkbox,
+            self.pos,
+            box_size,
+            self.font_ref,
+            &self.text,
+        )
+    }
+
+    /// Draw the label right of the box.
+    fn draw_label(&self, canvas: &mut Canvas, res: &Resources, box_size: (i32, i32)) {
+        if let Some(font_ref) = self.font_ref {
+            let (_, label_pos) = self.labeled_box(res, box_size);
+
+            draw_themed_text(
+                canvas,
+                res,
+                ControlType::Checkbox,
+                self.mouse.state.into(),
+                font_ref,
+                &self.text,
+                label_pos,
+            );
+        }
+    }
+}
+
+impl Checkbox<Flat> {
+    /// Create a new colored checkbox without a label.
+    pub fn new(size: (i32, i32), color: Color) -> Self {
+        Checkbox::with_show(Flat { size, color })
+    }
+}
+
+impl Control for Checkbox<Flat> {
+    fn update(&mut self, args: &ControlState, res: &Resources, events: &mut Vec<GuiEventKind>) {
+        let size = self.show.size;
+        self.update_state(args, res, size, events);
+    }
+
+    fn draw(&self, canvas: &mut Canvas, res: &Resources) {
+        draw_flat_box(
+            canvas,
+            res,
+            ControlType::Checkbox,
+            &self.show,
+            self.pos,
+            self.mouse.state,
+            self.checked,
+        );
+        self.draw_label(canvas, res, self.show.size);
+    }
+
+    fn control_type(&self) -> ControlType {
+        ControlType::Checkbox
+    }
+
+    fn pos(&self) -> (i32, i32) {
+        self.pos
+    }
+
+    fn set_pos(&mut self, x: i32, y: i32) {
+        self.pos = (x, y);
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.mouse.set_enabled(enabled);
+    }
+
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    fn activate(&mut self, events: &mut Vec<GuiEventKind>) {
+        self.toggle(events);
+    }
+
+    fn size(&self, res: &Resources) -> (i32, i32) {
+        self.area(res, self.show.size).size()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
+impl Checkbox<Image> {
+    /// Create a new checkbox with a sprite without a label.
+    ///
+    /// The image needs to be divided into 2 columns, the left unchecked and the right checked,
+    /// and 3 rows:
+    ///  1. normal state
+    ///  2. mouse hover state
+    ///  3. mouse pressed state
+    ///
+    /// ```compile_fail
+    /// +-------+-------+
+    /// |Normal | [x]   |
+    /// +-------+-------+
+    /// | Hover | [x]   |
+    /// +-------+-------+
+    /// |Pressed| [x]   |
+    /// +-------+-------+
+    /// ```
+    pub fn new_with_sprite(sprite_ref: SpriteRef) -> Self {
+        Checkbox::with_show(Image { sprite_ref })
+    }
+}
+
+impl Control for Checkbox<Image> {
+    fn update(&mut self, args: &ControlState, res: &Resources, events: &mut Vec<GuiEventKind>) {
+        let size = sprite_box_size(&self.show, res);
+        self.update_state(args, res, size, events);
+    }
+
+    fn draw(&self, canvas: &mut Canvas, res: &Resources) {
+        draw_sprite_box(
+            canvas,
+            res,
+            &self.show,
+            self.pos,
+            self.mouse.state,
+            self.checked,
+        );
+        self.draw_label(canvas, res, sprite_box_size(&self.show, res));
+    }
+
+    fn control_type(&self) -> ControlType {
+        ControlType::Checkbox
+    }
+
+    fn pos(&self) -> (i32, i32) {
+        self.pos
+    }
+
+    fn set_pos(&mut self, x: i32, y: i32) {
+        self.pos = (x, y);
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.mouse.set_enabled(enabled);
+    }
+
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    fn activate(&mut self, events: &mut Vec<GuiEventKind>) {
+        self.toggle(events);
+    }
+
+    fn size(&self, res: &Resources) -> (i32, i32) {
+        self.area(res, sprite_box_size(&self.show, res)).size()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
+/// A single option of a radio group.
+struct RadioOption {
+    text: String,
+    mouse: Clickable,
+}
+
+/// A list of options stacked vertically where only a single option can be selected.
+///
+/// It's rendered the same way as the `Checkbox`, with the options as the labels.
+///
+/// It emits `GuiEventKind::Selected` when another option is clicked.
+pub struct RadioGroup<S> {
+    /// How the boxes are rendered (`Flat` or `Image`).
+    show: S,
+
+    pos: (i32, i32),
+    font_ref: FontRef,
+
+    options: Vec<RadioOption>,
+    selected: Option<usize>,
+}
+
+impl<S> RadioGroup<S> {
+    fn with_show(show: S, font_ref: FontRef) -> Self {
+        RadioGroup {
+            show,
+            pos: (0, 0),
+            font_ref,
+            options: Vec::new(),
+            selected: None,
+        }
+    }
+
+    /// Retrieve the position.
+    pub fn pos(&self) -> (i32, i32) {
+        self.pos
+    }
+
+    /// Map a position.
+    pub fn with_pos(mut self, x: i32, y: i32) -> Self {
+        self.pos = (x, y);
+
+        self
+    }
+
+    /// Change the position.
+    pub fn set_pos(&mut self, x: i32, y: i32) {
+        self.pos = (x, y);
+    }
+
+    /// Add an option below the others.
+    pub fn with_option(mut self, text: &str) -> Self {
+        self.options.push(RadioOption {
+            text: String::from(text),
+            mouse: Clickable::new(),
+        });
+
+        self
+    }
+
+    /// Get the index of the selected option.
+    pub fn selected(&self) -> Option<usize> {
+        self.selected.filter(|&index| index < self.options.len())
+    }
+
+    /// Map the selected option, the option can also be added after this.
+    pub fn with_selected(mut self, index: usize) -> Self {
+        self.selected = Some(index);
+
+        self
+    }
+
+    /// Select an option, the others are deselected.
+    pub fn set_selected(&mut self, index: Option<usize>) {
+        self.selected = index.filter(|&index| index < self.options.len());
+    }
+
+    /// The area of each option with the position of the label.
+    fn option_areas(&self, res: &Resources, box_size: (i32, i32)) -> Vec<(Rect, (i32, i32))> {
+        let mut y = self.pos.1;
+
+        self.options
+            .iter()
+            .map(|option| {
+                let area = labeled_box(
+                    res,
+                    ControlType::RadioGroup,
+                    (self.pos.0, y),
+                    box_size,
+                    Some(self.font_ref),
+                    &option.text,
+                );
+                y = area.0.bottom() + OPTION_SPACING;
+
+                area
+            })
+            .collect()
+    }
+
+    /// The size of the area covered by all options.
+    fn group_size(&self, res: &Resources, box_size: (i32, i32)) -> (i32, i32) {
+        self.option_areas(res, box_size)
+            .iter()
+            .fold((0, 0), |size, (area, _)| {
+                (
+                    size.0.max(area.right() - self.pos.0),
+                    size.1.max(area.bottom() - self.pos.1),
+                )
+            })
+    }
+
+    /// Select the option that's clicked.
+    fn update_state(
+        &mut self,
+        args: &ControlState,
+        res: &Resources,
+        box_size: (i32, i32),
+        events: &mut Vec<GuiEventKind>,
+    ) {
+        let areas = self.option_areas(res, box_size);
+
+        for (index, (option, (area, _))) in self.options.iter_mut().zip(areas).enumerate() {
+            if option.mouse.update(args, area.pos(), area.size()) && self.selected != Some(index) {
+                self.selected = Some(index);
+                events.push(GuiEventKind::Selected(index));
+            }
+        }
+    }
+
+    /// Select the option after the selected one, after the last option the first is selected.
+    fn select_next(&mut self, events: &mut Vec<GuiEventKind>) {
+        if self.options.is_empty() {
+            return;
+        }
+
+        let index = self
+            .selected()
+            .map_or(0, |index| (index + 1) % self.options.len());
+        self.selected = Some(index);
+        events.push(GuiEventKind::Selected(index));
+    }
+
+    /// Draw the labels and the boxes using the function of the skin.
+    fn draw_options<F>(
+        &self,
+        canvas: &mut Canvas,
+        res: &Resources,
+        box_size: (i32, i32),
+        draw_box: F,
+    ) where
+        F: Fn(&mut Canvas, (i32, i32), ButtonState, bool),
+    {
+        let areas = self.option_areas(res, box_size);
+        for (index, (option, (area, label_pos))) in self.options.iter().zip(areas).enumerate() {
+            draw_box(
+                canvas,
+                area.pos(),
+                option.mouse.state,
+                self.selected == Some(index),
+            );
+            draw_themed_text(
+                canvas,
+                res,
+                ControlType::RadioGroup,
+                option.mouse.state.into(),
+                self.font_ref,
+                &option.text,
+                label_pos,
+            );
+        }
+    }
+}
+
+impl RadioGroup<Flat> {
+    /// Create a new group with colored boxes without options.
+    pub fn new(font_ref: FontRef, size: (i32, i32), color: Color) -> Self {
+        RadioGroup::with_show(Flat { size, color }, font_ref)
+    }
+}
+
+impl Control for RadioGroup<Flat> {
+    fn update(&mut self, args: &ControlState, res: &Resources, events: &mut Vec<GuiEventKind>) {
+        let size = self.show.size;
+        self.update_state(args, res, size, events);
+    }
+
+    fn draw(&self, canvas: &mut Canvas, res: &Resources) {
+        self.draw_options(
+            canvas,
+            res,
+            self.show.size,
+            |canvas, pos, state, checked| {
+                draw_flat_box(
+                    canvas,
+                    res,
+                    ControlType::RadioGroup,
+                    &self.show,
+                    pos,
+                    state,
+                    checked,
+                )
+            },
+        );
+    }
+
+    fn control_type(&self) -> ControlType {
+        ControlType::RadioGroup
+    }
+
+    fn pos(&self) -> (i32, i32) {
+        self.pos
+    }
+
+    fn set_pos(&mut self, x: i32, y: i32) {
+        self.pos = (x, y);
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        for option in self.options.iter_mut() {
+            option.mouse.set_enabled(enabled);
+        }
+    }
+
+    fn focusable(&self) -> bool {
+        !self.options.is_empty()
+    }
+
+    fn activate(&mut self, events: &mut Vec<GuiEventKind>) {
+        self.select_next(events);
+    }
+
+    fn size(&self, res: &Resources) -> (i32, i32) {
+        self.group_size(res, self.show.size)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
+impl RadioGroup<Image> {
+    /// Create a new group with sprite boxes without options.
+    ///
+    /// The image needs to be divided the same way as the image of `Checkbox::new_with_sprite`.
+    pub fn new_with_sprite(font_ref: FontRef, sprite_ref: SpriteRef) -> Self {
+        RadioGroup::with_show(Image { sprite_ref }, font_ref)
+    }
+}
+
+impl Control for RadioGroup<Image> {
+    fn update(&mut self, args: &ControlState, res: &Resources, events: &mut Vec<GuiEventKind>) {
+        let size = sprite_box_size(&self.show, res);
+        self.update_state(args, res, size, events);
+    }
+
+    fn draw(&self, canvas: &mut Canvas, res: &Resources) {
+        let size = sprite_box_size(&self.show, res);
+        self.draw_options(canvas, res, size, |canvas, pos, state, checked| {
+            draw_sprite_box(canvas, res, &self.show, pos, state, checked)
+        });
+    }
+
+    fn control_type(&self) -> ControlType {
+        ControlType::RadioGroup
+    }
+
+    fn pos(&self) -> (i32, i32) {
+        self.pos
+    }
+
+    fn set_pos(&mut self, x: i32, y: i32) {
+        self.pos = (x, y);
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        for option in self.options.iter_mut() {
+            option.mouse.set_enabled(enabled);
+        }
+    }
+
+    fn focusable(&self) -> bool {
+        !self.options.is_empty()
+    }
+
+    fn activate(&mut self, events: &mut Vec<GuiEventKind>) {
+        self.select_next(events);
+    }
+
+    fn size(&self, res: &Resources) -> (i32, i32) {
+        self.group_size(res, sprite_box_size(&self.show, res))
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn select_before_adding_options() {
+        let font_ref = Resources::new().default_font();
+        let group = RadioGroup::new(font_ref, (8, 8), Color::from_u32(0)).with_selected(1);
+        assert_eq!(group.selected(), None);
+
+        let group = group.with_option("Easy").with_option("Hard");
+        assert_eq!(group.selected(), Some(1));
+    }
+}
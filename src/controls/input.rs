@@ -0,0 +1,167 @@
+/// A key on the keyboard, independent of the windowing library.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Key {
+    A,
+    B,
+    C,
+    D,
+    E,
+    F,
+    G,
+    H,
+    I,
+    J,
+    K,
+    L,
+    M,
+    N,
+    O,
+    P,
+    Q,
+    R,
+    S,
+    T,
+    U,
+    V,
+    W,
+    X,
+    Y,
+    Z,
+    Key0,
+    Key1,
+    Key2,
+    Key3,
+    Key4,
+    Key5,
+    Key6,
+    Key7,
+    Key8,
+    Key9,
+    F1,
+    F2,
+    F3,
+    F4,
+    F5,
+    F6,
+    F7,
+    F8,
+    F9,
+    F10,
+    F11,
+    F12,
+    Up,
+    Down,
+    Left,
+    Right,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    Insert,
+    Delete,
+    Backspace,
+    Tab,
+    Enter,
+    Escape,
+    Space,
+    Shift,
+    Control,
+    Alt,
+    Logo,
+    /// Any other key, the value can be used by the game to identify it.
+    Other(u32),
+}
+
+/// A change of a key as reported by the windowing library.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum KeyEvent {
+    /// The key has been pressed, key repeats should also be reported as a press.
+    Pressed(Key),
+    /// The key has been released.
+    Released(Key),
+}
+
+/// What the player wants to do in a menu, independent of the controller or the input library.
+///
+/// A game maps the buttons and sticks of its gamepad, or any other device, to these intents. They
+/// act like the keys next to them.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum NavIntent {
+    /// Like `Key::Up`.
+    Up,
+    /// Like `Key::Down`.
+    Down,
+    /// Like `Key::Left`.
+    Left,
+    /// Like `Key::Right`.
+    Right,
+    /// Activate the focused control, like `Key::Enter`.
+    Accept,
+    /// Remove the focus from the focused control, like `Key::Escape`.
+    Cancel,
+    /// Like `Key::PageDown`.
+    PageNext,
+    /// Like `Key::PageUp`.
+    PagePrevious,
+}
+
+impl NavIntent {
+    /// The key the intent acts like.
+    pub fn key(self) -> Key {
+        match self {
+            NavIntent::Up => Key::Up,
+            NavIntent::Down => Key::Down,
+            NavIntent::Left => Key::Left,
+            NavIntent::Right => Key::Right,
+            NavIntent::Accept => Key::Enter,
+            NavIntent::Cancel => Key::Escape,
+            NavIntent::PageNext => Key::PageDown,
+            NavIntent::PagePrevious => Key::PageUp,
+        }
+    }
+}
+
+/// The modifier keys that are held down.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Modifiers {
+    pub shift: bool,
+    pub control: bool,
+    pub alt: bool,
+    pub logo: bool,
+}
+
+/// A button on the mouse.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum MouseButton {
+    Left,
+    Right,
+    Middle,
+}
+
+/// A set of mouse buttons.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct MouseButtons {
+    pub left: bool,
+    pub right: bool,
+    pub middle: bool,
+}
+
+impl MouseButtons {
+    /// Whether the button is part of the set.
+    pub fn contains(&self, button: MouseButton) -> bool {
+        match button {
+            MouseButton::Left => self.left,
+            MouseButton::Right => self.right,
+            MouseButton::Middle => self.middle,
+        }
+    }
+
+    /// The buttons which are in this set but not in the other.
+    pub fn difference(&self, other: MouseButtons) -> MouseButtons {
+        MouseButtons {
+            left: self.left && !other.left,
+            right: self.right && !other.right,
+            middle: self.middle && !other.middle,
+        }
+    }
+}
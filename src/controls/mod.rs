@@ -1,62 +1,233 @@
 mod button;
+mod checkbox;
+mod input;
+mod panel;
+mod progress_bar;
+mod slider;
 mod sprite;
 mod text;
+mod text_input;
 
-pub use self::button::{Button, ButtonState, Flat, Image};
+pub use self::button::{Button, ButtonCallback, ButtonState, Flat, Image, Sliced, StateCallback};
+pub use self::checkbox::{Checkbox, RadioGroup};
+pub use self::input::{Key, KeyEvent, Modifiers, MouseButton, MouseButtons, NavIntent};
+pub use self::panel::Panel;
+pub use self::progress_bar::{FillDirection, ProgressBar, ProgressSprites};
+pub use self::slider::{Orientation, Scrollbar, Slider, SliderSprites};
 pub use self::sprite::Sprite;
 pub use self::text::Label;
+pub use self::text_input::TextInput;
 
 use std::any::Any;
 
+use super::canvas::Canvas;
+use super::event::GuiEventKind;
+use super::layout::Layout;
+use super::rect::{Alignment, Insets, Rect};
 use super::resources::*;
+use super::theme::StyleState;
 
 /// Enum used to check what events should happen on certain controls.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub enum ControlType {
     Button,
+    Checkbox,
     Label,
+    Panel,
+    ProgressBar,
+    RadioGroup,
+    Scrollbar,
+    Slider,
     Sprite,
+    TextInput,
 }
 
 /// Data that needs to be supplied to the `update` function.
+#[derive(Debug, Default, Clone)]
 pub struct ControlState {
     /// The position of the mouse cursor. Is not required to be inside the bounds of the screen.
     pub mouse_pos: (i32, i32),
     /// If the left mouse button is pressed or not.
     pub mouse_down: bool,
+    /// If the right mouse button is pressed or not.
+    pub mouse_right_down: bool,
+    /// If the middle mouse button is pressed or not.
+    pub mouse_middle_down: bool,
+    /// How far the scroll wheel moved since the previous update, horizontally and vertically.
+    pub scroll_delta: (f32, f32),
+    /// The mouse buttons that went down since the previous update.
+    ///
+    /// This is computed by `Gui::update` from the previous state, it doesn't have to be supplied.
+    pub mouse_pressed: MouseButtons,
+    /// The mouse buttons that went up since the previous update.
+    ///
+    /// This is computed by `Gui::update` from the previous state, it doesn't have to be supplied.
+    pub mouse_released: MouseButtons,
+    /// The keys that have been pressed or released since the previous update, in order.
+    pub keys: Vec<KeyEvent>,
+    /// The characters that have been typed since the previous update.
+    pub text: String,
+    /// The modifier keys that are currently held down.
+    pub modifiers: Modifiers,
+    /// The navigation intents since the previous update, for example from a gamepad.
+    ///
+    /// `Gui::update` handles them as if the keys they act like are pressed after the keys, so the
+    /// controls receive them as `keys`. For the controls this is only informational.
+    pub nav: Vec<NavIntent>,
+    /// The mouse is above something else than the control, it can't hover over it.
+    ///
+    /// `Gui::update` sets this for controls behind another control under the mouse, and for the
+    /// child controls of a container when the mouse is outside of it.
+    pub mouse_occluded: bool,
 }
 
 impl ControlState {
     /// Determines if the mouse is inside a rectangle. Mostly used internally.
     pub fn mouse_collision(&self, pos: (i32, i32), size: (i32, i32)) -> bool {
-        self.mouse_pos.0 >= pos.0
+        !self.mouse_occluded
+            && self.mouse_pos.0 >= pos.0
             && self.mouse_pos.1 >= pos.1
             && self.mouse_pos.0 < pos.0 + size.0
             && self.mouse_pos.1 < pos.1 + size.1
     }
-}
 
-impl Default for ControlState {
-    fn default() -> Self {
-        ControlState {
-            mouse_pos: (0, 0),
-            mouse_down: false,
+    /// The mouse buttons that are currently held down.
+    pub fn mouse_buttons(&self) -> MouseButtons {
+        MouseButtons {
+            left: self.mouse_down,
+            right: self.mouse_right_down,
+            middle: self.mouse_middle_down,
         }
     }
+
+    /// Whether the mouse button is currently held down.
+    pub fn mouse_button_down(&self, button: MouseButton) -> bool {
+        self.mouse_buttons().contains(button)
+    }
+
+    /// Whether the mouse button went down since the previous update.
+    pub fn mouse_just_pressed(&self, button: MouseButton) -> bool {
+        self.mouse_pressed.contains(button)
+    }
+
+    /// Whether the mouse button went up since the previous update.
+    pub fn mouse_just_released(&self, button: MouseButton) -> bool {
+        self.mouse_released.contains(button)
+    }
+
+    /// Whether the key has been pressed since the previous update.
+    pub fn key_pressed(&self, key: Key) -> bool {
+        self.keys.contains(&KeyEvent::Pressed(key))
+    }
+
+    /// Whether the key has been released since the previous update.
+    pub fn key_released(&self, key: Key) -> bool {
+        self.keys.contains(&KeyEvent::Released(key))
+    }
+
+    /// Whether the navigation intent happened since the previous update.
+    pub fn nav_intent(&self, intent: NavIntent) -> bool {
+        self.nav.contains(&intent)
+    }
+}
+
+/// Draw a text with the font and the text color the theme has for a control type in a state.
+fn draw_themed_text(
+    canvas: &mut Canvas,
+    res: &Resources,
+    control_type: ControlType,
+    state: StyleState,
+    font_ref: FontRef,
+    text: &str,
+    pos: (i32, i32),
+) {
+    let font = res.get_themed_font(control_type, font_ref).unwrap();
+
+    match res.theme().style(control_type, state).text_color {
+        Some(color) => font.draw_string_tinted(canvas, text, pos, color),
+        None => font.draw_string(canvas, text, pos),
+    }
 }
 
 pub trait Control {
-    /// Update the control.
-    fn update(&mut self, args: &ControlState, res: &Resources);
+    /// Update the control, what happened to it can be pushed to the events.
+    fn update(&mut self, args: &ControlState, res: &Resources, events: &mut Vec<GuiEventKind>);
 
-    /// Draw the control on the output buffer.
-    fn draw(&self, buffer: &mut Vec<u32>, buffer_width: usize, res: &Resources);
+    /// Draw the control on the output canvas.
+    fn draw(&self, canvas: &mut Canvas, res: &Resources);
 
     /// Retrieve what type of control this is.
     fn control_type(&self) -> ControlType;
 
+    /// The position of the top left corner, relative to the container it's in.
+    fn pos(&self) -> (i32, i32);
+
+    /// Move the control, this is used by layouts.
+    fn set_pos(&mut self, x: i32, y: i32);
+
+    /// The size of the area the control covers.
+    fn size(&self, res: &Resources) -> (i32, i32);
+
+    /// The area the control covers, relative to the container it's in.
+    fn bounds(&self, res: &Resources) -> Rect {
+        Rect::from_pos_size(self.pos(), self.size(res))
+    }
+
+    /// The size the control needs to fit its content, for example the caption of a button.
+    ///
+    /// This can differ from `size` when the size is chosen by hand.
+    fn preferred_size(&self, res: &Resources) -> (i32, i32) {
+        self.size(res)
+    }
+
+    /// Called by the `Gui` when the control or one of its containers is enabled or disabled.
+    ///
+    /// Disabled controls are not updated, so this is the place to reset the state of the input.
+    fn set_enabled(&mut self, _enabled: bool) {}
+
+    /// Whether the control can receive the keyboard focus.
+    fn focusable(&self) -> bool {
+        false
+    }
+
+    /// Called by the `Gui` when the control receives or loses the keyboard focus.
+    fn set_focused(&mut self, _focused: bool) {}
+
+    /// Whether the control handles a key itself while it's focused, the `Gui` won't use the key to
+    /// move the focus or to activate the control.
+    fn captures_key(&self, _key: Key) -> bool {
+        false
+    }
+
+    /// Called by the `Gui` when the control is focused and Enter or Space is pressed, for
+    /// example a button is clicked.
+    fn activate(&mut self, _events: &mut Vec<GuiEventKind>) {}
+
+    /// Whether the control hides the mouse from the controls behind it when it's above it.
+    fn blocks_mouse(&self) -> bool {
+        true
+    }
+
+    /// How the child controls of a container are positioned, `None` when they are positioned by
+    /// hand.
+    fn layout(&self) -> Option<Layout> {
+        None
+    }
+
+    /// The area in which the child controls of a container are placed.
+    ///
+    /// The positions of the children are relative to the top left corner of the area and they
+    /// are clipped by it. Controls that can't have children return `None`.
+    fn content_area(&self, _res: &Resources) -> Option<Rect> {
+        None
+    }
+
     /// For downcasting.
     fn as_any(&self) -> &dyn Any;
 
     /// For downcasting.
     fn as_any_mut(&mut self) -> &mut dyn Any;
+
+    /// For downcasting owned controls.
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
 }
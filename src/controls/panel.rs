@@ -0,0 +1,149 @@
+use blit::Color;
+
+use super::*;
+
+/// What's drawn behind the children of a panel.
+#[derive(Debug)]
+enum Background {
+    None,
+    Flat(Color),
+    NineSlice(NineSliceRef),
+}
+
+/// A container for other controls.
+///
+/// Controls are added to it with `Gui::register_child`, their positions are relative to the top
+/// left corner of the panel and they are clipped by it. Moving, hiding or disabling the panel
+/// with the `Gui` moves, hides or disables them as well. With a `Layout` the children are
+/// positioned automatically.
+pub struct Panel {
+    pos: (i32, i32),
+    size: (i32, i32),
+
+    background: Background,
+    layout: Option<Layout>,
+}
+
+impl Panel {
+    /// Create a new transparent panel.
+    pub fn new(size: (i32, i32)) -> Self {
+        Panel {
+            pos: (0, 0),
+            size,
+            background: Background::None,
+            layout: None,
+        }
+    }
+
+    /// Retrieve the position.
+    pub fn pos(&self) -> (i32, i32) {
+        self.pos
+    }
+
+    /// Map a position.
+    pub fn with_pos(mut self, x: i32, y: i32) -> Self {
+        self.pos = (x, y);
+
+        self
+    }
+
+    /// Change the position.
+    pub fn set_pos(&mut self, x: i32, y: i32) {
+        self.pos = (x, y);
+    }
+
+    /// Retrieve the size.
+    pub fn size(&self) -> (i32, i32) {
+        self.size
+    }
+
+    /// Change the size.
+    pub fn set_size(&mut self, width: i32, height: i32) {
+        self.size = (width, height);
+    }
+
+    /// Map a color for the background, it's drawn with the border of the theme.
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.background = Background::Flat(color);
+
+        self
+    }
+
+    /// Map a nine-slice sprite to draw the background, the edges and the center are repeated to
+    /// fill it.
+    pub fn with_nine_slice(mut self, nine_slice_ref: NineSliceRef) -> Self {
+        self.background = Background::NineSlice(nine_slice_ref);
+
+        self
+    }
+
+    /// Map a layout which positions the children automatically.
+    pub fn with_layout(mut self, layout: Layout) -> Self {
+        self.layout = Some(layout);
+
+        self
+    }
+
+    /// Change the layout, with `None` the children keep the positions they have.
+    pub fn set_layout(&mut self, layout: Option<Layout>) {
+        self.layout = layout;
+    }
+}
+
+impl Control for Panel {
+    fn update(&mut self, _args: &ControlState, _res: &Resources, _events: &mut Vec<GuiEventKind>) {
+        // The children are updated by the `Gui`
+    }
+
+    fn draw(&self, canvas: &mut Canvas, res: &Resources) {
+        let rect = Rect::from_pos_size(self.pos, self.size);
+        match self.background {
+            Background::None => (),
+            Background::Flat(color) => res
+                .theme()
+                .style(ControlType::Panel, StyleState::Normal)
+                .draw_rect(canvas, res, rect, color),
+            Background::NineSlice(nine_slice_ref) => {
+                res.get_nine_slice(nine_slice_ref)
+                    .unwrap()
+                    .draw(canvas, rect);
+            }
+        }
+    }
+
+    fn control_type(&self) -> ControlType {
+        ControlType::Panel
+    }
+
+    fn pos(&self) -> (i32, i32) {
+        self.pos
+    }
+
+    fn set_pos(&mut self, x: i32, y: i32) {
+        self.pos = (x, y);
+    }
+
+    fn size(&self, _res: &Resources) -> (i32, i32) {
+        self.size
+    }
+
+    fn content_area(&self, _res: &Resources) -> Option<Rect> {
+        Some(Rect::from_pos_size(self.pos, self.size))
+    }
+
+    fn layout(&self) -> Option<Layout> {
+        self.layout
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
@@ -0,0 +1,315 @@
+use blit::Color;
+
+use super::*;
+
+/// The side from which a progress bar fills up.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum FillDirection {
+    LeftToRight,
+    RightToLeft,
+    TopToBottom,
+    BottomToTop,
+}
+
+/// The skin of the progress bar are sprites.
+#[derive(Debug)]
+pub struct ProgressSprites {
+    /// The empty bar, its size is the size of the progress bar.
+    pub background: SpriteRef,
+    /// The full bar, it's centered on top of the background.
+    pub fill: SpriteRef,
+}
+
+/// A bar which is filled relative to a value, for example a loading or a health bar.
+///
+/// It can be rendered in multiple ways:
+/// `Flat`: using a simpel pixel rectangle algorithm.
+/// `ProgressSprites`: using a background sprite with a fill sprite on top.
+pub struct ProgressBar<S> {
+    /// How the progress bar is rendered (`Flat` or `ProgressSprites`).
+    show: S,
+
+    pos: (i32, i32),
+    direction: FillDirection,
+
+    value: f64,
+    max: f64,
+
+    font_ref: Option<FontRef>,
+    text: String,
+
+    /// Scale the fill sprite instead of cutting it off.
+    fill_insets: Option<Insets>,
+}
+
+impl<S> ProgressBar<S> {
+    fn with_show(show: S) -> Self {
+        ProgressBar {
+            show,
+            pos: (0, 0),
+            direction: FillDirection::LeftToRight,
+            value: 0.0,
+            max: 1.0,
+            font_ref: None,
+            text: String::new(),
+            fill_insets: None,
+        }
+    }
+
+    /// Retrieve the position.
+    pub fn pos(&self) -> (i32, i32) {
+        self.pos
+    }
+
+    /// Map a position.
+    pub fn with_pos(mut self, x: i32, y: i32) -> Self {
+        self.pos = (x, y);
+
+        self
+    }
+
+    /// Change the position.
+    pub fn set_pos(&mut self, x: i32, y: i32) {
+        self.pos = (x, y);
+    }
+
+    /// Map the side from which the bar fills up, by default it's from left to right.
+    pub fn with_direction(mut self, direction: FillDirection) -> Self {
+        self.direction = direction;
+
+        self
+    }
+
+    /// Map the value at which the bar is full, by default it's `1.0`.
+    pub fn with_max(mut self, max: f64) -> Self {
+        self.max = max;
+
+        self
+    }
+
+    /// Map the value.
+    pub fn with_value(mut self, value: f64) -> Self {
+        self.value = value;
+
+        self
+    }
+
+    /// Get the value.
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    /// Change the value.
+    pub fn set_value(&mut self, value: f64) {
+        self.value = value;
+    }
+
+    /// How much of the bar is filled, from `0.0` to `1.0`.
+    pub fn fraction(&self) -> f64 {
+        if self.max <= 0.0 {
+            return 0.0;
+        }
+
+        (self.value / self.max).clamp(0.0, 1.0)
+    }
+
+    /// Map a text which is drawn in the center of the bar.
+    pub fn with_text(mut self, font_ref: FontRef, text: &str) -> Self {
+        self.font_ref = Some(font_ref);
+        self.text = String::from(text);
+
+        self
+    }
+
+    /// Change the text, it's only drawn when a font is mapped with `with_text`.
+    pub fn set_text(&mut self, text: &str) {
+        self.text = String::from(text);
+    }
+
+    /// The part of an area that's filled.
+    fn fill_rect(&self, rect: Rect) -> Rect {
+        let fraction = self.fraction();
+        let width = (f64::from(rect.width) * fraction).round() as i32;
+        let height = (f64::from(rect.height) * fraction).round() as i32;
+
+        match self.direction {
+            FillDirection::LeftToRight => Rect::new(rect.x, rect.y, width, rect.height),
+            FillDirection::RightToLeft => {
+                Rect::new(rect.right() - width, rect.y, width, rect.height)
+            }
+            FillDirection::TopToBottom => Rect::new(rect.x, rect.y, rect.width, height),
+            FillDirection::BottomToTop => {
+                Rect::new(rect.x, rect.bottom() - height, rect.width, height)
+            }
+        }
+    }
+
+    /// Draw the text in the center of the bar.
+    fn draw_text(&self, canvas: &mut Canvas, res: &Resources, size: (i32, i32)) {
+        let font_ref = match self.font_ref {
+            Some(font_ref) if !self.text.is_empty() => font_ref,
+            _ => return,
+        };
+
+        let text_size = res
+            .get_themed_font(ControlType::ProgressBar, font_ref)
+            .unwrap()
+            .text_size(&self.text);
+        let pos = (
+            self.pos.0 + (size.0 - text_size.0) / 2,
+            self.pos.1 + (size.1 - text_size.1) / 2,
+        );
+        draw_themed_text(
+            canvas,
+            res,
+            ControlType::ProgressBar,
+            StyleState::Normal,
+            font_ref,
+            &self.text,
+            pos,
+        );
+    }
+}
+
+impl ProgressBar<Flat> {
+    /// Create a new colored progress bar, the color is the color of the filled part.
+    pub fn new(size: (i32, i32), color: Color) -> Self {
+        ProgressBar::with_show(Flat { size, color })
+    }
+}
+
+impl Control for ProgressBar<Flat> {
+    fn update(&mut self, _args: &ControlState, _res: &Resources, _events: &mut Vec<GuiEventKind>) {}
+
+    fn draw(&self, canvas: &mut Canvas, res: &Resources) {
+        let style = res
+            .theme()
+            .style(ControlType::ProgressBar, StyleState::Normal);
+        let color = style.fill_color(self.show.color);
+
+        // The empty part is a darker version of the filled part
+        let rect = Rect::from_pos_size(self.pos, self.show.size);
+        canvas.fill_rect(rect, Color::from_u32(color.u32() & 0x33_33_33));
+        canvas.fill_rect(self.fill_rect(rect), color);
+        style.draw_border(canvas, rect);
+
+        self.draw_text(canvas, res, self.show.size);
+    }
+
+    fn control_type(&self) -> ControlType {
+        ControlType::ProgressBar
+    }
+
+    fn pos(&self) -> (i32, i32) {
+        self.pos
+    }
+
+    fn set_pos(&mut self, x: i32, y: i32) {
+        self.pos = (x, y);
+    }
+
+    fn size(&self, _res: &Resources) -> (i32, i32) {
+        self.show.size
+    }
+
+    fn blocks_mouse(&self) -> bool {
+        false
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
+impl ProgressBar<ProgressSprites> {
+    /// Create a new progress bar with sprites.
+    ///
+    /// By default the part of the fill sprite that's not filled is cut off, use
+    /// `with_fill_insets` to scale it instead.
+    pub fn new_with_sprites(background: SpriteRef, fill: SpriteRef) -> Self {
+        ProgressBar::with_show(ProgressSprites { background, fill })
+    }
+
+    /// Scale the fill sprite to the filled part like a nine-slice, the corners defined by the
+    /// insets are kept intact while the edges and the center are scaled.
+    ///
+    /// The parts are scaled instead of repeated so a gradient in the fill doesn't show seams.
+    pub fn with_fill_insets(mut self, insets: Insets) -> Self {
+        self.fill_insets = Some(insets);
+
+        self
+    }
+}
+
+impl Control for ProgressBar<ProgressSprites> {
+    fn update(&mut self, _args: &ControlState, _res: &Resources, _events: &mut Vec<GuiEventKind>) {}
+
+    fn draw(&self, canvas: &mut Canvas, res: &Resources) {
+        let background = res.get_sprite(self.show.background).unwrap();
+        let size = background.size();
+        canvas.blit(background, self.pos, Rect::from_pos_size((0, 0), size));
+
+        let fill = res.get_sprite(self.show.fill).unwrap();
+        let fill_size = fill.size();
+        let full = Rect::from_pos_size(
+            (
+                self.pos.0 + (size.0 - fill_size.0) / 2,
+                self.pos.1 + (size.1 - fill_size.1) / 2,
+            ),
+            fill_size,
+        );
+        let filled = self.fill_rect(full);
+
+        match self.fill_insets {
+            Some(insets) => canvas.blit_nine_slice_scaled(
+                fill,
+                Rect::from_pos_size((0, 0), fill_size),
+                insets,
+                filled,
+            ),
+            None => canvas.blit(fill, filled.pos(), filled.translate(-full.x, -full.y)),
+        }
+
+        self.draw_text(canvas, res, size);
+    }
+
+    fn control_type(&self) -> ControlType {
+        ControlType::ProgressBar
+    }
+
+    fn pos(&self) -> (i32, i32) {
+        self.pos
+    }
+
+    fn set_pos(&mut self, x: i32, y: i32) {
+        self.pos = (x, y);
+    }
+
+    fn size(&self, res: &Resources) -> (i32, i32) {
+        res.get_sprite(self.show.background).unwrap().size()
+    }
+
+    fn blocks_mouse(&self) -> bool {
+        false
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
@@ -0,0 +1,722 @@
+use blit::Color;
+
+use super::button::Clickable;
+use super::*;
+
+/// The minimum length of the thumb of a scrollbar.
+const MIN_THUMB_LENGTH: i32 = 4;
+
+/// The amount of steps the page up and page down keys move.
+const PAGE_STEPS: f64 = 10.0;
+
+/// The direction in which a control is laid out.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Orientation {
+    /// From left to right.
+    Horizontal,
+    /// From top to bottom.
+    Vertical,
+}
+
+/// The skin of the slider are sprites.
+#[derive(Debug)]
+pub struct SliderSprites {
+    /// The background of the slider, its size is the size of the slider.
+    pub track: SpriteRef,
+    /// The part that's dragged, it's divided into 3 parts just like `Button::new_with_sprite`.
+    pub thumb: SpriteRef,
+}
+
+/// A control to pick a value from a range by dragging a thumb along a track.
+///
+/// It can be rendered in multiple ways:
+/// `Flat`: using a simpel pixel rectangle algorithm.
+/// `SliderSprites`: using a sprite for the track and a spritesheet for the thumb.
+///
+/// When the `Gui` focuses it, for example after clicking on it, the value can also be changed with
+/// the arrow keys along the slider and the page up, page down, home and end keys. The value can be
+/// changed with the scroll wheel while hovering over it. The value grows to the right and
+/// downwards, so the right, down and page down keys increase it.
+///
+/// It emits `GuiEventKind::ValueChanged` when the value is changed by the user.
+pub struct Slider<S> {
+    /// How the slider is rendered (`Flat` or `SliderSprites`).
+    show: S,
+
+    pos: (i32, i32),
+    orientation: Orientation,
+
+    min: f64,
+    max: f64,
+    step: Option<f64>,
+    value: f64,
+
+    mouse: Clickable,
+    /// Where the thumb is grabbed relative to its start while it's dragged.
+    grab_offset: Option<i32>,
+    focused: bool,
+    enabled: bool,
+}
+
+impl<S> Slider<S> {
+    fn with_show(show: S) -> Self {
+        Slider {
+            show,
+            pos: (0, 0),
+            orientation: Orientation::Horizontal,
+            min: 0.0,
+            max: 1.0,
+            step: None,
+            value: 0.0,
+            mouse: Clickable::new(),
+            grab_offset: None,
+            focused: false,
+            enabled: true,
+        }
+    }
+
+    /// Retrieve the position.
+    pub fn pos(&self) -> (i32, i32) {
+        self.pos
+    }
+
+    /// Map a position.
+    pub fn with_pos(mut self, x: i32, y: i32) -> Self {
+        self.pos = (x, y);
+
+        self
+    }
+
+    /// Change the position.
+    pub fn set_pos(&mut self, x: i32, y: i32) {
+        self.pos = (x, y);
+    }
+
+    /// Map the direction the thumb moves in, by default it's horizontal.
+    pub fn with_orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = orientation;
+
+        self
+    }
+
+    /// Map the range of the value, by default it's from `0.0` to `1.0`.
+    pub fn with_range(mut self, min: f64, max: f64) -> Self {
+        self.min = min;
+        self.max = max;
+        self.value = self.snap(self.value);
+
+        self
+    }
+
+    /// Map the step the value is snapped to, a step of `1.0` results in integer values.
+    pub fn with_step(mut self, step: f64) -> Self {
+        self.step = Some(step).filter(|&step| step > 0.0);
+        self.value = self.snap(self.value);
+
+        self
+    }
+
+    /// Map the value.
+    pub fn with_value(mut self, value: f64) -> Self {
+        self.set_value(value);
+
+        self
+    }
+
+    /// Get the value.
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    /// Get the value rounded to an integer, useful when the step is `1.0`.
+    pub fn value_i32(&self) -> i32 {
+        self.value.round() as i32
+    }
+
+    /// Change the value, it's clamped to the range and snapped to the step.
+    pub fn set_value(&mut self, value: f64) {
+        self.value = self.snap(value);
+    }
+
+    /// Get if the slider is receiving keyboard input.
+    pub fn focused(&self) -> bool {
+        self.focused
+    }
+
+    /// Give or take away the keyboard focus.
+    pub fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    /// Clamp a value to the range and round it to the nearest step.
+    fn snap(&self, value: f64) -> f64 {
+        let value = match self.step {
+            Some(step) => self.min + ((value - self.min) / step).round() * step,
+            None => value,
+        };
+
+        value
+            .max(self.min.min(self.max))
+            .min(self.max.max(self.min))
+    }
+
+    /// How much the value changes with a single key press or scroll.
+    fn nudge_amount(&self) -> f64 {
+        self.step.unwrap_or((self.max - self.min) / 100.0)
+    }
+
+    /// The distance along the orientation and the size across it.
+    fn along(&self, size: (i32, i32)) -> (i32, i32) {
+        match self.orientation {
+            Orientation::Horizontal => size,
+            Orientation::Vertical => (size.1, size.0),
+        }
+    }
+
+    /// The area of the thumb with a certain length.
+    fn thumb_rect(&self, size: (i32, i32), thumb_length: i32) -> Rect {
+        let (length, thickness) = self.along(size);
+
+        let range = self.max - self.min;
+        let ratio = if range == 0.0 {
+            0.0
+        } else {
+            (self.value - self.min) / range
+        };
+        let offset = ((length - thumb_length).max(0) as f64 * ratio).round() as i32;
+
+        match self.orientation {
+            Orientation::Horizontal => {
+                Rect::new(self.pos.0 + offset, self.pos.1, thumb_length, thickness)
+            }
+            Orientation::Vertical => {
+                Rect::new(self.pos.0, self.pos.1 + offset, thickness, thumb_length)
+            }
+        }
+    }
+
+    /// Handle dragging, scrolling and the keys.
+    fn update_state(
+        &mut self,
+        args: &ControlState,
+        size: (i32, i32),
+        thumb_length: i32,
+        events: &mut Vec<GuiEventKind>,
+    ) {
+        let prev_value = self.value;
+
+        let thumb = self.thumb_rect(size, thumb_length);
+        self.mouse.update(args, thumb.pos(), thumb.size());
+
+        let (mouse, start) = match self.orientation {
+            Orientation::Horizontal => (args.mouse_pos.0, self.pos.0),
+            Orientation::Vertical => (args.mouse_pos.1, self.pos.1),
+        };
+        let thumb_start = match self.orientation {
+            Orientation::Horizontal => thumb.x,
+            Orientation::Vertical => thumb.y,
+        };
+
+        let hovering = args.mouse_collision(self.pos, size);
+        if args.mouse_just_pressed(MouseButton::Left) {
+            if hovering {
+                // When the track is clicked the center of the thumb jumps to the mouse
+                self.grab_offset = Some(if thumb.contains(args.mouse_pos) {
+                    mouse - thumb_start
+                } else {
+                    thumb_length / 2
+                });
+            }
+        } else if !args.mouse_down {
+            self.grab_offset = None;
+        }
+
+        if let Some(grab_offset) = self.grab_offset {
+            let (length, _) = self.along(size);
+            let free_length = (length - thumb_length).max(1);
+            let ratio = f64::from(mouse - grab_offset - start) / f64::from(free_length);
+
+            self.value = self.snap(self.min + ratio * (self.max - self.min));
+        }
+
+        if hovering {
+            let scroll = match self.orientation {
+                Orientation::Horizontal => args.scroll_delta.0 - args.scroll_delta.1,
+                Orientation::Vertical => -args.scroll_delta.1,
+            };
+            if scroll != 0.0 {
+                self.value =
+                    self.snap(self.value + f64::from(scroll.signum()) * self.nudge_amount());
+            }
+        }
+
+        if self.focused {
+            let nudge = self.nudge_amount();
+            for key_event in args.keys.iter() {
+                let key = match *key_event {
+                    KeyEvent::Pressed(key) if self.value_key(key) => key,
+                    _ => continue,
+                };
+
+                // The value grows to the right and downwards like the thumb
+                self.value = match key {
+                    Key::Left | Key::Up => self.snap(self.value - nudge),
+                    Key::Right | Key::Down => self.snap(self.value + nudge),
+                    Key::PageUp => self.snap(self.value - nudge * PAGE_STEPS),
+                    Key::PageDown => self.snap(self.value + nudge * PAGE_STEPS),
+                    Key::Home => self.snap(self.min),
+                    Key::End => self.snap(self.max),
+                    _ => self.value,
+                };
+            }
+        }
+
+        if self.value != prev_value {
+            events.push(GuiEventKind::ValueChanged(self.value));
+        }
+    }
+
+    /// Stop dragging and lose the focus when the slider is disabled.
+    fn change_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        if !enabled {
+            self.mouse = Clickable::new();
+            self.grab_offset = None;
+            self.focused = false;
+        }
+    }
+
+    /// Whether the key changes the value instead of moving the focus away.
+    fn value_key(&self, key: Key) -> bool {
+        match key {
+            Key::Left | Key::Right => self.orientation == Orientation::Horizontal,
+            Key::Up | Key::Down => self.orientation == Orientation::Vertical,
+            Key::PageUp | Key::PageDown | Key::Home | Key::End => true,
+            _ => false,
+        }
+    }
+
+    /// The state of the thumb for the style.
+    fn thumb_state(&self) -> StyleState {
+        if !self.enabled {
+            StyleState::Disabled
+        } else if self.grab_offset.is_some() {
+            StyleState::Pressed
+        } else if self.mouse.state != ButtonState::Normal {
+            StyleState::Hover
+        } else {
+            StyleState::Normal
+        }
+    }
+}
+
+impl Slider<Flat> {
+    /// Create a new colored slider.
+    pub fn new(size: (i32, i32), color: Color) -> Self {
+        Slider::with_show(Flat { size, color })
+    }
+
+    /// The length of a thumb that's as long as the slider is thick.
+    fn flat_thumb_length(&self) -> i32 {
+        self.along(self.show.size).1
+    }
+
+    /// Draw the track and the thumb with the style of the control type.
+    fn draw_flat(
+        &self,
+        canvas: &mut Canvas,
+        res: &Resources,
+        control_type: ControlType,
+        thumb_length: i32,
+    ) {
+        let theme = res.theme();
+
+        // The track is a darker version of the thumb
+        let track_style = if self.enabled {
+            theme.style(control_type, StyleState::Normal)
+        } else {
+            theme.style(control_type, StyleState::Disabled)
+        };
+        let track = Rect::from_pos_size(self.pos, self.show.size);
+        let track_color = track_style.fill_color(self.show.color).u32() & 0x55_55_55;
+        canvas.fill_rect(track, Color::from_u32(track_color));
+        track_style.draw_border(canvas, track);
+
+        let thumb = self.thumb_rect(self.show.size, thumb_length);
+        theme.style(control_type, self.thumb_state()).draw_rect(
+            canvas,
+            res,
+            thumb,
+            self.show.color,
+        );
+        if self.focused {
+            theme
+                .style(control_type, StyleState::Focused)
+                .draw_border(canvas, thumb);
+        }
+    }
+}
+
+impl Control for Slider<Flat> {
+    fn update(&mut self, args: &ControlState, _res: &Resources, events: &mut Vec<GuiEventKind>) {
+        let size = self.show.size;
+        let thumb_length = self.flat_thumb_length();
+        self.update_state(args, size, thumb_length, events);
+    }
+
+    fn draw(&self, canvas: &mut Canvas, res: &Resources) {
+        self.draw_flat(canvas, res, ControlType::Slider, self.flat_thumb_length());
+    }
+
+    fn control_type(&self) -> ControlType {
+        ControlType::Slider
+    }
+
+    fn pos(&self) -> (i32, i32) {
+        self.pos
+    }
+
+    fn set_pos(&mut self, x: i32, y: i32) {
+        self.pos = (x, y);
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.change_enabled(enabled);
+    }
+
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    fn captures_key(&self, key: Key) -> bool {
+        self.value_key(key)
+    }
+
+    fn size(&self, _res: &Resources) -> (i32, i32) {
+        self.show.size
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
+impl Slider<SliderSprites> {
+    /// Create a new slider with sprites.
+    ///
+    /// The size of the slider is the size of the track sprite. The thumb sprite needs to be 3
+    /// thumbs divided vertically:
+    ///  1. normal state
+    ///  2. mouse hover state
+    ///  3. dragging state
+    pub fn new_with_sprites(track: SpriteRef, thumb: SpriteRef) -> Self {
+        Slider::with_show(SliderSprites { track, thumb })
+    }
+
+    /// The size of the track and the size of a single thumb.
+    fn sprite_sizes(&self, res: &Resources) -> ((i32, i32), (i32, i32)) {
+        let track = res.get_sprite(self.show.track).unwrap().size();
+        let thumb = res.get_sprite(self.show.thumb).unwrap().size();
+
+        (track, (thumb.0, thumb.1 / 3))
+    }
+}
+
+impl Control for Slider<SliderSprites> {
+    fn update(&mut self, args: &ControlState, res: &Resources, events: &mut Vec<GuiEventKind>) {
+        let (track_size, thumb_size) = self.sprite_sizes(res);
+        let thumb_length = self.along(thumb_size).0;
+        self.update_state(args, track_size, thumb_length, events);
+    }
+
+    fn draw(&self, canvas: &mut Canvas, res: &Resources) {
+        let (track_size, thumb_size) = self.sprite_sizes(res);
+
+        let track = res.get_sprite(self.show.track).unwrap();
+        canvas.blit(track, self.pos, Rect::from_pos_size((0, 0), track_size));
+
+        let thumb_rect = self.thumb_rect(track_size, self.along(thumb_size).0);
+        let height_offset = if self.grab_offset.is_some() {
+            thumb_size.1 * 2
+        } else if self.mouse.state != ButtonState::Normal {
+            thumb_size.1
+        } else {
+            0
+        };
+
+        // Center the thumb across the track
+        let (_, thickness) = self.along(track_size);
+        let (_, thumb_thickness) = self.along(thumb_size);
+        let center = (thickness - thumb_thickness) / 2;
+        let pos = match self.orientation {
+            Orientation::Horizontal => (thumb_rect.x, thumb_rect.y + center),
+            Orientation::Vertical => (thumb_rect.x + center, thumb_rect.y),
+        };
+
+        let thumb = res.get_sprite(self.show.thumb).unwrap();
+        canvas.blit(
+            thumb,
+            pos,
+            Rect::new(0, height_offset, thumb_size.0, thumb_size.1),
+        );
+    }
+
+    fn control_type(&self) -> ControlType {
+        ControlType::Slider
+    }
+
+    fn pos(&self) -> (i32, i32) {
+        self.pos
+    }
+
+    fn set_pos(&mut self, x: i32, y: i32) {
+        self.pos = (x, y);
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.change_enabled(enabled);
+    }
+
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    fn captures_key(&self, key: Key) -> bool {
+        self.value_key(key)
+    }
+
+    fn size(&self, res: &Resources) -> (i32, i32) {
+        self.sprite_sizes(res).0
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
+/// A slider where the size of the thumb shows how much of the content is visible.
+///
+/// The value is the offset in pixels of the visible part of the content.
+///
+/// It emits `GuiEventKind::ValueChanged` when the offset is changed by the user.
+pub struct Scrollbar {
+    slider: Slider<Flat>,
+
+    content_size: i32,
+    viewport_size: i32,
+}
+
+impl Scrollbar {
+    /// Create a new colored scrollbar for content which is partially visible in the viewport.
+    pub fn new(size: (i32, i32), color: Color, content_size: i32, viewport_size: i32) -> Self {
+        let mut scrollbar = Scrollbar {
+            slider: Slider::new(size, color).with_step(1.0),
+            content_size: 0,
+            viewport_size: 0,
+        };
+        scrollbar.set_content_size(content_size, viewport_size);
+
+        scrollbar
+    }
+
+    /// Retrieve the position.
+    pub fn pos(&self) -> (i32, i32) {
+        self.slider.pos()
+    }
+
+    /// Map a position.
+    pub fn with_pos(mut self, x: i32, y: i32) -> Self {
+        self.slider.set_pos(x, y);
+
+        self
+    }
+
+    /// Change the position.
+    pub fn set_pos(&mut self, x: i32, y: i32) {
+        self.slider.set_pos(x, y);
+    }
+
+    /// Map the direction the thumb moves in, by default it's horizontal.
+    pub fn with_orientation(mut self, orientation: Orientation) -> Self {
+        self.slider.orientation = orientation;
+
+        self
+    }
+
+    /// Get the offset of the visible part of the content.
+    pub fn offset(&self) -> i32 {
+        self.slider.value_i32()
+    }
+
+    /// Change the offset of the visible part of the content.
+    pub fn set_offset(&mut self, offset: i32) {
+        self.slider.set_value(f64::from(offset));
+    }
+
+    /// Change the size of the content and the part of it that's visible.
+    pub fn set_content_size(&mut self, content_size: i32, viewport_size: i32) {
+        self.content_size = content_size;
+        self.viewport_size = viewport_size;
+
+        let offset = self.offset();
+        self.slider.max = f64::from((content_size - viewport_size).max(0));
+        self.set_offset(offset);
+    }
+
+    /// The length of the thumb relative to the visible part of the content.
+    fn thumb_length(&self) -> i32 {
+        let (length, _) = self.slider.along(self.slider.show.size);
+        if self.content_size <= self.viewport_size {
+            return length;
+        }
+
+        (length * self.viewport_size / self.content_size.max(1)).max(MIN_THUMB_LENGTH)
+    }
+}
+
+impl Control for Scrollbar {
+    fn update(&mut self, args: &ControlState, _res: &Resources, events: &mut Vec<GuiEventKind>) {
+        let size = self.slider.show.size;
+        let thumb_length = self.thumb_length();
+        self.slider.update_state(args, size, thumb_length, events);
+    }
+
+    fn draw(&self, canvas: &mut Canvas, res: &Resources) {
+        self.slider
+            .draw_flat(canvas, res, ControlType::Scrollbar, self.thumb_length());
+    }
+
+    fn control_type(&self) -> ControlType {
+        ControlType::Scrollbar
+    }
+
+    fn pos(&self) -> (i32, i32) {
+        self.slider.pos
+    }
+
+    fn set_pos(&mut self, x: i32, y: i32) {
+        self.slider.pos = (x, y);
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        self.slider.change_enabled(enabled);
+    }
+
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.slider.focused = focused;
+    }
+
+    fn captures_key(&self, key: Key) -> bool {
+        self.slider.value_key(key)
+    }
+
+    fn size(&self, _res: &Resources) -> (i32, i32) {
+        self.slider.show.size
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Update a focused control with a key pressed.
+    fn press<C: Control>(control: &mut C, key: Key) {
+        control.set_focused(true);
+        control.update(
+            &ControlState {
+                keys: vec![KeyEvent::Pressed(key)],
+                ..ControlState::default()
+            },
+            &Resources::new(),
+            &mut Vec::new(),
+        );
+    }
+
+    #[test]
+    fn vertical_keys_follow_the_thumb() {
+        let mut slider = Slider::new((10, 100), Color::from_u32(0))
+            .with_orientation(Orientation::Vertical)
+            .with_range(0.0, 10.0)
+            .with_step(1.0)
+            .with_value(5.0);
+        press(&mut slider, Key::Down);
+        assert_eq!(slider.value(), 6.0);
+        press(&mut slider, Key::Up);
+        assert_eq!(slider.value(), 5.0);
+
+        let mut slider = Slider::new((100, 10), Color::from_u32(0))
+            .with_range(0.0, 10.0)
+            .with_step(1.0)
+            .with_value(5.0);
+        press(&mut slider, Key::Right);
+        assert_eq!(slider.value(), 6.0);
+        press(&mut slider, Key::Left);
+        assert_eq!(slider.value(), 5.0);
+    }
+
+    #[test]
+    fn horizontal_slider_pages_like_arrows() {
+        let mut slider = Slider::new((100, 10), Color::from_u32(0))
+            .with_range(0.0, 100.0)
+            .with_step(1.0)
+            .with_value(50.0);
+        press(&mut slider, Key::PageDown);
+        assert!(slider.value() > 50.0);
+        press(&mut slider, Key::PageUp);
+        assert_eq!(slider.value(), 50.0);
+
+        // The up and down keys are left to the `Gui` to move the focus
+        press(&mut slider, Key::Down);
+        assert_eq!(slider.value(), 50.0);
+    }
+
+    #[test]
+    fn vertical_scrollbar_pages_down() {
+        let mut scrollbar = Scrollbar::new((10, 100), Color::from_u32(0), 1000, 100)
+            .with_orientation(Orientation::Vertical);
+        press(&mut scrollbar, Key::PageDown);
+        assert!(scrollbar.offset() > 0);
+        press(&mut scrollbar, Key::PageUp);
+        assert_eq!(scrollbar.offset(), 0);
+    }
+}
@@ -34,21 +34,16 @@ impl Sprite {
 }
 
 impl Control for Sprite {
-    fn update(&mut self, _args: &ControlState, _res: &Resources) {
+    fn update(&mut self, _args: &ControlState, _res: &Resources, _events: &mut Vec<GuiEventKind>) {
         // NoOp
     }
 
-    fn draw(&self, buffer: &mut Vec<u32>, buffer_width: usize, res: &Resources) {
+    fn draw(&self, canvas: &mut Canvas, res: &Resources) {
         let sprite = res.get_sprite(self.sprite_ref).unwrap();
 
         let draw_size = sprite.size();
 
-        sprite.blit_rect(
-            buffer,
-            buffer_width,
-            self.pos,
-            (0, 0, draw_size.0, draw_size.1),
-        );
+        canvas.blit(sprite, self.pos, Rect::from_pos_size((0, 0), draw_size));
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -59,7 +54,27 @@ impl Control for Sprite {
         ControlType::Sprite
     }
 
+    fn pos(&self) -> (i32, i32) {
+        self.pos
+    }
+
+    fn set_pos(&mut self, x: i32, y: i32) {
+        self.pos = (x, y);
+    }
+
+    fn size(&self, res: &Resources) -> (i32, i32) {
+        res.get_sprite(self.sprite_ref).unwrap().size()
+    }
+
+    fn blocks_mouse(&self) -> bool {
+        false
+    }
+
     fn as_any_mut(&mut self) -> &mut dyn Any {
         self
     }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
 }
@@ -55,18 +55,42 @@ impl Label {
 }
 
 impl Control for Label {
-    fn update(&mut self, _args: &ControlState, _res: &Resources) {}
-
-    fn draw(&self, buffer: &mut Vec<u32>, buffer_width: usize, res: &Resources) {
-        let font = res.get_font(self.font_ref).unwrap();
-
-        font.draw_string(buffer, buffer_width, &self.text, self.pos);
+    fn update(&mut self, _args: &ControlState, _res: &Resources, _events: &mut Vec<GuiEventKind>) {}
+
+    fn draw(&self, canvas: &mut Canvas, res: &Resources) {
+        draw_themed_text(
+            canvas,
+            res,
+            ControlType::Label,
+            StyleState::Normal,
+            self.font_ref,
+            &self.text,
+            self.pos,
+        );
     }
 
     fn control_type(&self) -> ControlType {
         ControlType::Label
     }
 
+    fn pos(&self) -> (i32, i32) {
+        self.pos
+    }
+
+    fn set_pos(&mut self, x: i32, y: i32) {
+        self.pos = (x, y);
+    }
+
+    fn size(&self, res: &Resources) -> (i32, i32) {
+        res.get_themed_font(ControlType::Label, self.font_ref)
+            .unwrap()
+            .text_size(&self.text)
+    }
+
+    fn blocks_mouse(&self) -> bool {
+        false
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
@@ -74,4 +98,8 @@ impl Control for Label {
     fn as_any_mut(&mut self) -> &mut dyn Any {
         self
     }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
 }
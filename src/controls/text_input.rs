@@ -0,0 +1,543 @@
+use blit::Color;
+
+use super::*;
+
+/// The space between the border and the text.
+const PADDING: i32 = 3;
+
+const SELECTION_COLOR: u32 = 0x44_66_AA;
+const CARET_COLOR: u32 = 0xFF_FF_FF;
+
+/// A single line text field which can be edited by the user.
+///
+/// The `Gui` focuses the field when it's clicked. When focused it handles typed text, the left,
+/// right, home and end keys to move the caret, shift to select, backspace & delete to remove text,
+/// control-A to select everything and enter to submit the text. The `Gui` removes the focus when
+/// escape is pressed or with `NavIntent::Cancel`.
+///
+/// The up and down keys are left to the `Gui` to move the focus to another control.
+///
+/// It emits `GuiEventKind::TextChanged` when the text is edited and `GuiEventKind::Submitted` when
+/// enter is pressed.
+pub struct TextInput {
+    font_ref: FontRef,
+
+    pos: (i32, i32),
+    width: i32,
+
+    text: String,
+    placeholder: String,
+    max_length: Option<usize>,
+    password_mask: Option<char>,
+
+    /// Draw the background with a nine-slice sprite instead of the flat colors.
+    nine_slice_ref: Option<NineSliceRef>,
+
+    /// The character index of the caret.
+    caret: usize,
+    /// The character index of the other side of the selection.
+    anchor: Option<usize>,
+    /// The first visible character when the text doesn't fit.
+    scroll: usize,
+
+    focused: bool,
+    /// The mouse has been pressed inside the field and is still held.
+    dragging: bool,
+}
+
+impl TextInput {
+    /// Create a new empty text field with a width in pixels, the height is determined by the font.
+    pub fn new(font_ref: FontRef, width: i32) -> Self {
+        TextInput {
+            font_ref,
+            pos: (0, 0),
+            width,
+            text: String::new(),
+            placeholder: String::new(),
+            max_length: None,
+            password_mask: None,
+            nine_slice_ref: None,
+            caret: 0,
+            anchor: None,
+            scroll: 0,
+            focused: false,
+            dragging: false,
+        }
+    }
+
+    /// Retrieve the position.
+    pub fn pos(&self) -> (i32, i32) {
+        self.pos
+    }
+
+    /// Map a position.
+    pub fn with_pos(mut self, x: i32, y: i32) -> Self {
+        self.pos = (x, y);
+
+        self
+    }
+
+    /// Change the position.
+    pub fn set_pos(&mut self, x: i32, y: i32) {
+        self.pos = (x, y);
+    }
+
+    /// Get the text.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Map the text.
+    pub fn with_text(mut self, text: &str) -> Self {
+        self.set_text(text);
+
+        self
+    }
+
+    /// Replace the text, the caret is moved to the end.
+    pub fn set_text(&mut self, text: &str) {
+        self.text = match self.max_length {
+            Some(max_length) => text.chars().take(max_length).collect(),
+            None => String::from(text),
+        };
+        self.caret = self.len();
+        self.anchor = None;
+        self.scroll = 0;
+    }
+
+    /// Map the text shown when the field is empty.
+    pub fn with_placeholder(mut self, placeholder: &str) -> Self {
+        self.placeholder = String::from(placeholder);
+
+        self
+    }
+
+    /// Map the maximum amount of characters.
+    pub fn with_max_length(mut self, max_length: usize) -> Self {
+        self.max_length = Some(max_length);
+        let text = self.text.clone();
+        self.set_text(&text);
+
+        self
+    }
+
+    /// Map a character which is shown instead of the real characters, for example `'*'`.
+    pub fn with_password_mask(mut self, mask: char) -> Self {
+        self.password_mask = Some(mask);
+
+        self
+    }
+
+    /// Map a nine-slice sprite to draw the background of the field, the edges and the center are
+    /// repeated to fill it.
+    ///
+    /// The image needs to be divided vertically into 2 parts, the first is used when the field
+    /// isn't focused and the second when it is.
+    pub fn with_nine_slice(mut self, nine_slice_ref: NineSliceRef) -> Self {
+        self.nine_slice_ref = Some(nine_slice_ref);
+
+        self
+    }
+
+    /// Whether the field is receiving keyboard input.
+    pub fn focused(&self) -> bool {
+        self.focused
+    }
+
+    /// Give or take away the keyboard focus.
+    pub fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+        if !focused {
+            self.anchor = None;
+            self.dragging = false;
+        }
+    }
+
+    /// The selected text, empty if nothing is selected.
+    pub fn selected_text(&self) -> &str {
+        match self.selection() {
+            Some((start, end)) => &self.text[self.byte_index(start)..self.byte_index(end)],
+            None => "",
+        }
+    }
+
+    /// The start and end character index of the selection.
+    fn selection(&self) -> Option<(usize, usize)> {
+        match self.anchor {
+            Some(anchor) if anchor != self.caret => {
+                Some((anchor.min(self.caret), anchor.max(self.caret)))
+            }
+            _ => None,
+        }
+    }
+
+    /// The amount of characters.
+    fn len(&self) -> usize {
+        self.text.chars().count()
+    }
+
+    /// Convert a character index to a byte index of the text.
+    fn byte_index(&self, index: usize) -> usize {
+        self.text
+            .char_indices()
+            .nth(index)
+            .map_or(self.text.len(), |(byte, _)| byte)
+    }
+
+    /// Remove the characters between the character indices.
+    fn remove(&mut self, start: usize, end: usize) {
+        let range = self.byte_index(start)..self.byte_index(end);
+        self.text.replace_range(range, "");
+
+        self.caret = start;
+        self.anchor = None;
+    }
+
+    /// Remove the selection, returns false when nothing was selected.
+    fn remove_selection(&mut self) -> bool {
+        match self.selection() {
+            Some((start, end)) => {
+                self.remove(start, end);
+
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Insert a character at the caret, replacing the selection.
+    fn insert(&mut self, ch: char) -> bool {
+        let removed = self.remove_selection();
+
+        if self.max_length.is_some_and(|max| self.len() >= max) {
+            return removed;
+        }
+
+        let index = self.byte_index(self.caret);
+        self.text.insert(index, ch);
+        self.caret += 1;
+
+        true
+    }
+
+    /// Move the caret, extending the selection when `select` is set.
+    fn move_caret(&mut self, caret: usize, select: bool) {
+        if select {
+            self.anchor.get_or_insert(self.caret);
+        } else {
+            self.anchor = None;
+        }
+
+        self.caret = caret.min(self.len());
+    }
+
+    /// Handle a key press, returns true when the text changed.
+    fn key_pressed(&mut self, key: Key, modifiers: Modifiers) -> bool {
+        let select = modifiers.shift;
+
+        match key {
+            Key::Left => match self.selection() {
+                Some((start, _)) if !select => self.move_caret(start, false),
+                _ => self.move_caret(self.caret.saturating_sub(1), select),
+            },
+            Key::Right => match self.selection() {
+                Some((_, end)) if !select => self.move_caret(end, false),
+                _ => self.move_caret(self.caret + 1, select),
+            },
+            Key::Home => self.move_caret(0, select),
+            Key::End => self.move_caret(self.len(), select),
+            Key::A if modifiers.control => {
+                self.anchor = Some(0);
+                self.caret = self.len();
+            }
+            Key::Backspace => {
+                if !self.remove_selection() {
+                    if self.caret == 0 {
+                        return false;
+                    }
+                    self.remove(self.caret - 1, self.caret);
+                }
+
+                return true;
+            }
+            Key::Delete => {
+                if !self.remove_selection() {
+                    if self.caret >= self.len() {
+                        return false;
+                    }
+                    self.remove(self.caret, self.caret + 1);
+                }
+
+                return true;
+            }
+            _ => (),
+        }
+
+        false
+    }
+
+    /// The character index closest to a horizontal position.
+    fn index_at(&self, x: i32, char_width: i32) -> usize {
+        let offset = (x - self.pos.0 - PADDING + char_width / 2).max(0) / char_width.max(1);
+
+        (self.scroll + offset as usize).min(self.len())
+    }
+
+    /// The amount of characters that fit in the field.
+    fn visible_chars(&self, char_width: i32) -> usize {
+        ((self.width - PADDING * 2).max(0) / char_width.max(1)) as usize
+    }
+}
+
+impl Control for TextInput {
+    fn update(&mut self, args: &ControlState, res: &Resources, events: &mut Vec<GuiEventKind>) {
+        let char_size = res
+            .get_themed_font(ControlType::TextInput, self.font_ref)
+            .unwrap()
+            .char_size();
+        let size = self.size(res);
+
+        if args.mouse_just_pressed(MouseButton::Left) {
+            // The field is already focused by the `Gui` when it's clicked
+            if self.focused && args.mouse_collision(self.pos, size) {
+                let index = self.index_at(args.mouse_pos.0, char_size.0);
+                self.move_caret(index, args.modifiers.shift);
+                self.dragging = true;
+            }
+        } else if self.dragging {
+            if args.mouse_down {
+                let index = self.index_at(args.mouse_pos.0, char_size.0);
+                self.move_caret(index, true);
+            } else {
+                self.dragging = false;
+            }
+        }
+
+        if !self.focused {
+            return;
+        }
+
+        let mut changed = false;
+        let mut submitted = false;
+        for key_event in args.keys.iter() {
+            match *key_event {
+                KeyEvent::Pressed(Key::Enter) => submitted = true,
+                KeyEvent::Pressed(key) => changed |= self.key_pressed(key, args.modifiers),
+                KeyEvent::Released(_) => (),
+            }
+        }
+
+        for ch in args.text.chars().filter(|ch| !ch.is_control()) {
+            changed |= self.insert(ch);
+        }
+
+        if changed {
+            events.push(GuiEventKind::TextChanged(self.text.clone()));
+        }
+        if submitted {
+            events.push(GuiEventKind::Submitted(self.text.clone()));
+        }
+
+        // Scroll the text so the caret is always visible
+        let visible = self.visible_chars(char_size.0);
+        if self.caret < self.scroll {
+            self.scroll = self.caret;
+        } else if self.caret > self.scroll + visible {
+            self.scroll = self.caret - visible;
+        }
+        self.scroll = self.scroll.min(self.len().saturating_sub(visible));
+    }
+
+    fn draw(&self, canvas: &mut Canvas, res: &Resources) {
+        let font = res
+            .get_themed_font(ControlType::TextInput, self.font_ref)
+            .unwrap();
+        let char_size = font.char_size();
+
+        let style = res.theme().style(
+            ControlType::TextInput,
+            if self.focused {
+                StyleState::Focused
+            } else {
+                StyleState::Normal
+            },
+        );
+
+        let rect = self.bounds(res);
+        match self.nine_slice_ref {
+            Some(nine_slice_ref) => {
+                let nine_slice = res.get_nine_slice(nine_slice_ref).unwrap();
+                nine_slice.draw_row(canvas, self.focused as i32, 2, rect);
+            }
+            // Without a color in the style the background is black
+            None => style.draw_rect(canvas, res, rect, Color::from_u32(0)),
+        }
+
+        let text_pos = (self.pos.0 + PADDING, self.pos.1 + PADDING);
+        let visible = self.visible_chars(char_size.0);
+        let draw_text = |canvas: &mut Canvas, text: String| match style.text_color {
+            Some(color) => font.draw_string_tinted(canvas, text, text_pos, color),
+            None => font.draw_string(canvas, text, text_pos),
+        };
+
+        if self.text.is_empty() {
+            if !self.focused {
+                let placeholder: String = self.placeholder.chars().take(visible).collect();
+                draw_text(canvas, placeholder);
+            }
+        } else {
+            if let Some((start, end)) = self.selection() {
+                let start = start.max(self.scroll) - self.scroll;
+                let end = end.min(self.scroll + visible).saturating_sub(self.scroll);
+                if end > start {
+                    canvas.fill_rect(
+                        Rect::new(
+                            text_pos.0 + start as i32 * char_size.0,
+                            text_pos.1,
+                            (end - start) as i32 * char_size.0,
+                            char_size.1,
+                        ),
+                        Color::from_u32(SELECTION_COLOR),
+                    );
+                }
+            }
+
+            let shown: String = match self.password_mask {
+                Some(mask) => mask
+                    .to_string()
+                    .repeat(self.len().saturating_sub(self.scroll).min(visible)),
+                None => self.text.chars().skip(self.scroll).take(visible).collect(),
+            };
+            draw_text(canvas, shown);
+        }
+
+        if self.focused {
+            let caret_x = text_pos.0 + self.caret.saturating_sub(self.scroll) as i32 * char_size.0;
+            canvas.fill_rect(
+                Rect::new(caret_x, text_pos.1 - 1, 1, char_size.1 + 2),
+                style
+                    .text_color
+                    .unwrap_or_else(|| Color::from_u32(CARET_COLOR)),
+            );
+        }
+    }
+
+    fn control_type(&self) -> ControlType {
+        ControlType::TextInput
+    }
+
+    fn pos(&self) -> (i32, i32) {
+        self.pos
+    }
+
+    fn set_pos(&mut self, x: i32, y: i32) {
+        self.pos = (x, y);
+    }
+
+    fn set_enabled(&mut self, enabled: bool) {
+        if !enabled {
+            self.set_focused(false);
+        }
+    }
+
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        TextInput::set_focused(self, focused);
+    }
+
+    fn captures_key(&self, key: Key) -> bool {
+        // Every key edits the text, except the ones to move to another control or to leave it
+        !matches!(key, Key::Tab | Key::Up | Key::Down | Key::Escape)
+    }
+
+    fn size(&self, res: &Resources) -> (i32, i32) {
+        let char_size = res
+            .get_themed_font(ControlType::TextInput, self.font_ref)
+            .unwrap()
+            .char_size();
+
+        (self.width, char_size.1 + PADDING * 2)
+    }
+
+    fn preferred_size(&self, res: &Resources) -> (i32, i32) {
+        let char_size = res
+            .get_themed_font(ControlType::TextInput, self.font_ref)
+            .unwrap()
+            .char_size();
+
+        match self.max_length {
+            // Room for the caret behind the last character
+            Some(max_length) => (
+                max_length as i32 * char_size.0 + PADDING * 2 + 1,
+                char_size.1 + PADDING * 2,
+            ),
+            None => self.size(res),
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Update a focused field with keys pressed.
+    fn press(input: &mut TextInput, res: &Resources, keys: &[Key]) -> Vec<GuiEventKind> {
+        let mut events = Vec::new();
+        input.set_focused(true);
+        input.update(
+            &ControlState {
+                keys: keys.iter().cloned().map(KeyEvent::Pressed).collect(),
+                ..ControlState::default()
+            },
+            res,
+            &mut events,
+        );
+
+        events
+    }
+
+    #[test]
+    fn up_and_down_move_the_focus() {
+        let res = Resources::new();
+        let mut input = TextInput::new(res.default_font(), 100).with_text("abc");
+        assert!(!input.captures_key(Key::Up));
+        assert!(!input.captures_key(Key::Down));
+
+        // The caret doesn't move, the keys are only used by the `Gui`
+        press(&mut input, &res, &[Key::Left, Key::Up]);
+        assert_eq!(input.caret, 2);
+        press(&mut input, &res, &[Key::Down]);
+        assert_eq!(input.caret, 2);
+    }
+
+    #[test]
+    fn edits_in_a_single_update() {
+        let res = Resources::new();
+        let mut input = TextInput::new(res.default_font(), 100).with_text("abc");
+        let keys = [Key::Backspace, Key::Enter, Key::Home, Key::Delete];
+        let events = press(&mut input, &res, &keys);
+        assert_eq!(input.text(), "b");
+        assert_eq!(
+            events,
+            vec![
+                GuiEventKind::TextChanged("b".into()),
+                GuiEventKind::Submitted("b".into())
+            ]
+        );
+    }
+}
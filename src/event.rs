@@ -0,0 +1,30 @@
+use super::ControlRef;
+
+/// What happened to a control.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GuiEventKind {
+    /// The mouse has been pressed and released above the control.
+    Clicked,
+    /// The mouse started hovering over the control.
+    HoverEntered,
+    /// The mouse stopped hovering over the control.
+    HoverLeft,
+    /// The text of the control has been edited by the user.
+    TextChanged(String),
+    /// The text of the control has been confirmed by the user.
+    Submitted(String),
+    /// The value of the control has been changed by the user.
+    ValueChanged(f64),
+    /// The control has been checked or unchecked by the user.
+    Toggled(bool),
+    /// The option with the index has been selected by the user.
+    Selected(usize),
+}
+
+/// An event emitted by a control during `Gui::update`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GuiEvent {
+    /// The control that emitted the event.
+    pub control: ControlRef,
+    pub kind: GuiEventKind,
+}
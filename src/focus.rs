@@ -0,0 +1,100 @@
+use blit::Color;
+
+use super::canvas::Canvas;
+use super::rect::Rect;
+use super::resources::{NineSliceRef, Resources};
+use super::ControlRef;
+
+/// How the control with the keyboard focus is marked.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum FocusIndicator {
+    /// Nothing is drawn, for when the controls show that they are focused themselves.
+    None,
+    /// A rectangle around the control, the padding is the space between it and the control.
+    Outline { color: Color, padding: i32 },
+    /// A nine-slice sprite around the control, the edges and the center are repeated to fit it.
+    NineSlice {
+        nine_slice_ref: NineSliceRef,
+        padding: i32,
+    },
+}
+
+impl FocusIndicator {
+    /// Draw the indicator around the area of the focused control.
+    pub(crate) fn draw(&self, canvas: &mut Canvas, res: &Resources, bounds: Rect) {
+        match *self {
+            FocusIndicator::None => (),
+            FocusIndicator::Outline { color, padding } => {
+                canvas.stroke_rect(bounds.grow(padding), color)
+            }
+            FocusIndicator::NineSlice {
+                nine_slice_ref,
+                padding,
+            } => res
+                .get_nine_slice(nine_slice_ref)
+                .unwrap()
+                .draw(canvas, bounds.grow(padding)),
+        }
+    }
+}
+
+impl Default for FocusIndicator {
+    fn default() -> Self {
+        FocusIndicator::Outline {
+            color: Color::from_u32(0xFF_FF_00),
+            padding: 1,
+        }
+    }
+}
+
+/// The direction of an arrow key.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+/// Sort the controls from top to bottom and from left to right, the order in which the focus
+/// moves with the tab key.
+pub(crate) fn sort_reading_order(candidates: &mut [(ControlRef, Rect)]) {
+    candidates.sort_by_key(|(_, rect)| (rect.y, rect.x));
+}
+
+/// The control closest to an area in a direction.
+///
+/// Controls which are further away from the line through the center of the area in the direction
+/// count as further away.
+pub(crate) fn nearest(
+    from: Rect,
+    direction: Direction,
+    candidates: &[(ControlRef, Rect)],
+) -> Option<ControlRef> {
+    let from_center = center(from);
+
+    candidates
+        .iter()
+        .filter_map(|&(control_ref, rect)| {
+            let to_center = center(rect);
+            let (along, across) = match direction {
+                Direction::Up => (from_center.1 - to_center.1, to_center.0 - from_center.0),
+                Direction::Down => (to_center.1 - from_center.1, to_center.0 - from_center.0),
+                Direction::Left => (from_center.0 - to_center.0, to_center.1 - from_center.1),
+                Direction::Right => (to_center.0 - from_center.0, to_center.1 - from_center.1),
+            };
+
+            if along > 0 {
+                Some((along + across.abs() * 2, control_ref))
+            } else {
+                None
+            }
+        })
+        .min_by_key(|&(distance, _)| distance)
+        .map(|(_, control_ref)| control_ref)
+}
+
+/// The center of a rectangle.
+fn center(rect: Rect) -> (i32, i32) {
+    (rect.x + rect.width / 2, rect.y + rect.height / 2)
+}
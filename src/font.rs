@@ -1,61 +1,222 @@
-use blit::{BlitBuffer, Color};
+use blit::Color;
+use std::ops::RangeInclusive;
 
-#[derive(Debug, Copy, Clone)]
+use super::bitmap::Bitmap;
+use super::canvas::Canvas;
+use super::rect::Rect;
+
+/// The amount of characters a tab advances.
+const TAB_WIDTH: i32 = 4;
+
+/// Maps a range of characters to consecutive cells in the font sheet.
+///
+/// The cells are counted from left to right and top to bottom, so a font sheet can have multiple
+/// rows of characters.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct GlyphRange {
+    pub chars: RangeInclusive<char>,
+    pub cell: usize,
+}
+
+impl GlyphRange {
+    /// Map the characters to the cells starting at `cell`.
+    pub fn new(chars: RangeInclusive<char>, cell: usize) -> Self {
+        GlyphRange { chars, cell }
+    }
+
+    /// The cell of the character if it's in the range.
+    fn cell(&self, ch: char) -> Option<usize> {
+        if self.chars.contains(&ch) {
+            Some(self.cell + (ch as usize - *self.chars.start() as usize))
+        } else {
+            None
+        }
+    }
+}
+
+/// How the characters are laid out in a font sheet.
+///
+/// ```rust
+/// use direct_gui::{FontSettings, GlyphRange};
+///
+/// // A sheet with the ASCII characters followed by the German umlauts
+/// let settings = FontSettings {
+///     glyphs: vec![
+///         GlyphRange::new('Ä'..='Ä', 94),
+///         GlyphRange::new('Ö'..='Ö', 95),
+///         GlyphRange::new('Ü'..='Ü', 96),
+///     ],
+///     ..FontSettings::default()
+/// };
+/// ```
+#[derive(Debug, Clone)]
 pub struct FontSettings {
+    /// The character in the first cell, all cells after it are the following characters.
     pub start: char,
     pub char_size: (usize, usize),
     pub leading_offset: i32,
     pub mask_color: Color,
+    /// Characters which don't follow `start`, these take precedence.
+    pub glyphs: Vec<GlyphRange>,
+    /// The character drawn when the font doesn't contain a character, `None` leaves it empty.
+    pub fallback: Option<char>,
+}
+
+impl Default for FontSettings {
+    /// The settings of the default font.
+    fn default() -> Self {
+        FontSettings {
+            start: '!',
+            char_size: (9, 9),
+            leading_offset: 2,
+            mask_color: Color::from_u32(0xFF_00_FF),
+            glyphs: Vec::new(),
+            fallback: Some('?'),
+        }
+    }
 }
 
 #[derive(Debug)]
 pub struct Font {
-    buffer: BlitBuffer,
+    buffer: Bitmap,
     settings: FontSettings,
+
+    /// The amount of characters on a single row of the font sheet.
+    columns: usize,
+    /// The total amount of characters in the font sheet.
+    cells: usize,
 }
 
 impl Font {
-    pub fn new(buffer: BlitBuffer, settings: FontSettings) -> Self {
-        Font { buffer, settings }
+    pub fn new<B: Into<Bitmap>>(buffer: B, settings: FontSettings) -> Self {
+        let buffer = buffer.into();
+        let columns = buffer.width() as usize / settings.char_size.0.max(1);
+        let rows = buffer.height() as usize / settings.char_size.1.max(1);
+
+        Font {
+            buffer,
+            settings,
+            columns,
+            cells: columns * rows,
+        }
     }
 
-    pub fn draw_char(&self, buffer: &mut Vec<u32>, buffer_width: usize, ch: char, pos: (i32, i32)) {
-        let offset = ch as u8 - self.settings.start as u8;
-        let rect = (
-            offset as i32 * self.settings.char_size.0 as i32,
-            0,
+    /// The size of a single character in pixels.
+    pub fn char_size(&self) -> (i32, i32) {
+        (
             self.settings.char_size.0 as i32,
             self.settings.char_size.1 as i32,
-        );
-        self.buffer.blit_rect(buffer, buffer_width, pos, rect);
+        )
+    }
+
+    /// The size of the area in pixels a string is drawn in.
+    pub fn text_size(&self, string: &str) -> (i32, i32) {
+        let (char_width, char_height) = self.char_size();
+
+        let mut width = 0;
+        let mut max_width = 0;
+        let mut lines = 1;
+        for ch in string.chars() {
+            match ch {
+                '\n' => {
+                    lines += 1;
+                    width = 0;
+                }
+                '\t' => width += char_width * TAB_WIDTH,
+                ch if ch.is_control() => (),
+                _ => width += char_width,
+            }
+            max_width = max_width.max(width);
+        }
+
+        (
+            max_width,
+            lines * char_height + (lines - 1) * self.settings.leading_offset,
+        )
     }
 
-    pub fn draw_string<S: Into<String>>(
+    /// Draw a single character, characters not in the font are drawn as the fallback character.
+    pub fn draw_char(&self, canvas: &mut Canvas, ch: char, pos: (i32, i32)) {
+        self.draw_glyph(canvas, ch, pos, None);
+    }
+
+    pub fn draw_string<S: Into<String>>(&self, canvas: &mut Canvas, string: S, pos: (i32, i32)) {
+        self.draw_glyphs(canvas, &string.into(), pos, None);
+    }
+
+    /// Draw a string with the colors of the font multiplied by a color.
+    ///
+    /// For a white font this results in text with the color.
+    pub fn draw_string_tinted<S: Into<String>>(
         &self,
-        buffer: &mut Vec<u32>,
-        buffer_width: usize,
+        canvas: &mut Canvas,
         string: S,
         pos: (i32, i32),
+        tint: Color,
     ) {
-        let string = string.into();
+        self.draw_glyphs(canvas, &string.into(), pos, Some(tint));
+    }
+
+    /// Draw a single character, optionally tinted.
+    fn draw_glyph(&self, canvas: &mut Canvas, ch: char, pos: (i32, i32), tint: Option<Color>) {
+        let cell = match self
+            .cell(ch)
+            .or_else(|| self.settings.fallback.and_then(|ch| self.cell(ch)))
+        {
+            Some(cell) => cell,
+            None => return,
+        };
+
+        let (width, height) = self.settings.char_size;
+        let rect = Rect::new(
+            ((cell % self.columns) * width) as i32,
+            ((cell / self.columns) * height) as i32,
+            width as i32,
+            height as i32,
+        );
+        match tint {
+            Some(tint) => canvas.blit_tinted(&self.buffer, pos, rect, tint),
+            None => canvas.blit(&self.buffer, pos, rect),
+        }
+    }
+
+    /// Draw all characters of a string, optionally tinted.
+    fn draw_glyphs(&self, canvas: &mut Canvas, string: &str, pos: (i32, i32), tint: Option<Color>) {
         let chars = string.chars();
 
+        let char_width = self.settings.char_size.0 as i32;
+
         let mut new_pos = pos;
         for ch in chars.into_iter() {
             match ch {
                 ' ' => {
-                    new_pos.0 += self.settings.char_size.0 as i32;
+                    new_pos.0 += char_width;
+                }
+                '\t' => {
+                    new_pos.0 += char_width * TAB_WIDTH;
                 }
                 '\n' => {
                     // Align left, start on a newline on linebreak
                     new_pos.0 = pos.0;
                     new_pos.1 += self.settings.char_size.1 as i32 + self.settings.leading_offset;
                 }
+                // Other control characters such as '\r' don't take up space
+                ch if ch.is_control() => (),
                 ch => {
-                    self.draw_char(buffer, buffer_width, ch, new_pos);
-                    new_pos.0 += self.settings.char_size.0 as i32;
+                    self.draw_glyph(canvas, ch, new_pos, tint);
+                    new_pos.0 += char_width;
                 }
             }
         }
     }
+
+    /// Find the cell in the font sheet of a character.
+    fn cell(&self, ch: char) -> Option<usize> {
+        self.settings
+            .glyphs
+            .iter()
+            .find_map(|glyphs| glyphs.cell(ch))
+            .or_else(|| (ch as usize).checked_sub(self.settings.start as usize))
+            .filter(|&cell| cell < self.cells)
+    }
 }
@@ -0,0 +1,210 @@
+use super::rect::{Alignment, Insets};
+
+/// In which order the controls are placed.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Arrangement {
+    Row,
+    Column,
+    Grid(usize),
+}
+
+/// Positions controls automatically based on their size.
+///
+/// A layout is used by a container such as a `Panel` for its children, or by the `Gui` for the
+/// top level controls. The positions are computed again every update, so the controls move when
+/// their size or the size of the container changes.
+///
+/// ```rust
+/// use direct_gui::{Alignment, Insets, Layout};
+///
+/// // A centered column of controls with 4 pixels between them
+/// let layout = Layout::column()
+///     .with_spacing(4)
+///     .with_padding(Insets::uniform(8))
+///     .with_align(Alignment::Center, Alignment::Center);
+/// ```
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Layout {
+    arrangement: Arrangement,
+    spacing: (i32, i32),
+    padding: Insets,
+    align: (Alignment, Alignment),
+}
+
+impl Layout {
+    /// Place the controls next to each other from left to right.
+    pub fn row() -> Self {
+        Layout::with_arrangement(Arrangement::Row)
+    }
+
+    /// Place the controls below each other from top to bottom.
+    pub fn column() -> Self {
+        Layout::with_arrangement(Arrangement::Column)
+    }
+
+    /// Place the controls in a grid with a fixed amount of columns, filled from left to right
+    /// and top to bottom.
+    ///
+    /// Every column is as wide as its widest control and every row as high as its highest
+    /// control.
+    pub fn grid(columns: usize) -> Self {
+        Layout::with_arrangement(Arrangement::Grid(columns.max(1)))
+    }
+
+    fn with_arrangement(arrangement: Arrangement) -> Self {
+        Layout {
+            arrangement,
+            spacing: (0, 0),
+            padding: Insets::default(),
+            align: (Alignment::Start, Alignment::Start),
+        }
+    }
+
+    /// Map the space between the controls.
+    pub fn with_spacing(mut self, spacing: i32) -> Self {
+        self.spacing = (spacing, spacing);
+
+        self
+    }
+
+    /// Map the space between the columns and the rows of a grid separately.
+    pub fn with_grid_spacing(mut self, horizontal: i32, vertical: i32) -> Self {
+        self.spacing = (horizontal, vertical);
+
+        self
+    }
+
+    /// Map the space between the edges of the container and the controls.
+    pub fn with_padding(mut self, padding: Insets) -> Self {
+        self.padding = padding;
+
+        self
+    }
+
+    /// Map where the controls are placed inside the container and inside their row, column or
+    /// cell, by default it's the top left.
+    pub fn with_align(mut self, horizontal: Alignment, vertical: Alignment) -> Self {
+        self.align = (horizontal, vertical);
+
+        self
+    }
+
+    /// Compute the positions of controls with the sizes inside an area with a size.
+    pub fn arrange(&self, area: (i32, i32), sizes: &[(i32, i32)]) -> Vec<(i32, i32)> {
+        let columns = match self.arrangement {
+            Arrangement::Row => sizes.len().max(1),
+            Arrangement::Column => 1,
+            Arrangement::Grid(columns) => columns,
+        };
+        let rows = sizes.len().div_ceil(columns);
+
+        // The size of the cells is determined by the largest control in it's column or row
+        let mut widths = vec![0; columns];
+        let mut heights = vec![0; rows];
+        for (index, size) in sizes.iter().enumerate() {
+            widths[index % columns] = widths[index % columns].max(size.0);
+            heights[index / columns] = heights[index / columns].max(size.1);
+        }
+
+        let inner = (
+            area.0 - self.padding.left - self.padding.right,
+            area.1 - self.padding.top - self.padding.bottom,
+        );
+        let total = (
+            widths.iter().sum::<i32>() + self.spacing.0 * (columns as i32 - 1).max(0),
+            heights.iter().sum::<i32>() + self.spacing.1 * (rows as i32 - 1).max(0),
+        );
+        let start = (
+            self.padding.left + self.align.0.offset(inner.0, total.0),
+            self.padding.top + self.align.1.offset(inner.1, total.1),
+        );
+
+        let column_starts = cell_starts(start.0, &widths, self.spacing.0);
+        let row_starts = cell_starts(start.1, &heights, self.spacing.1);
+
+        sizes
+            .iter()
+            .enumerate()
+            .map(|(index, size)| {
+                let (column, row) = (index % columns, index / columns);
+
+                (
+                    column_starts[column] + self.align.0.offset(widths[column], size.0),
+                    row_starts[row] + self.align.1.offset(heights[row], size.1),
+                )
+            })
+            .collect()
+    }
+}
+
+/// The start positions of consecutive cells with the sizes.
+fn cell_starts(start: i32, sizes: &[i32], spacing: i32) -> Vec<i32> {
+    let mut pos = start;
+
+    sizes
+        .iter()
+        .map(|size| {
+            let cell_start = pos;
+            pos += size + spacing;
+
+            cell_start
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn row_with_spacing() {
+        let sizes = [(10, 5), (20, 8), (5, 5)];
+        let layout = Layout::row().with_spacing(2);
+        assert_eq!(
+            layout.arrange((100, 50), &sizes),
+            vec![(0, 0), (12, 0), (34, 0)]
+        );
+
+        // Centered in the row, which is centered in the area
+        let layout = layout.with_align(Alignment::Start, Alignment::Center);
+        assert_eq!(
+            layout.arrange((100, 50), &sizes),
+            vec![(0, 22), (12, 21), (34, 22)]
+        );
+    }
+
+    #[test]
+    fn column_with_padding() {
+        let layout = Layout::column()
+            .with_spacing(3)
+            .with_padding(Insets::uniform(4))
+            .with_align(Alignment::End, Alignment::End);
+        assert_eq!(
+            layout.arrange((50, 100), &[(10, 10), (20, 5)]),
+            vec![(36, 78), (26, 91)]
+        );
+    }
+
+    #[test]
+    fn grid_with_remainder() {
+        let sizes = [(10, 10), (20, 4), (6, 6)];
+        let layout = Layout::grid(2).with_grid_spacing(1, 2);
+        assert_eq!(
+            layout.arrange((40, 30), &sizes),
+            vec![(0, 0), (11, 0), (0, 12)]
+        );
+
+        // The odd space left over is rounded down
+        let layout = layout.with_align(Alignment::Center, Alignment::Center);
+        assert_eq!(
+            layout.arrange((40, 30), &sizes),
+            vec![(4, 6), (15, 9), (6, 18)]
+        );
+    }
+
+    #[test]
+    fn no_controls() {
+        assert_eq!(Layout::grid(3).arrange((10, 10), &[]), vec![]);
+        assert_eq!(Layout::row().arrange((10, 10), &[]), vec![]);
+    }
+}
@@ -39,24 +39,50 @@
 //! };
 //! gui.update(&cs);
 //!
+//! // Handle what happened to the controls during the update
+//! for event in gui.events() {
+//!     println!("{:?}", event.kind);
+//! }
+//!
 //! // Finally render the current gui state to the buffer
 //! gui.draw_to_buffer(&mut buffer);
 //! ```
 
 #[cfg(feature = "file-loading")]
 use std::path::Path;
-use std::{error::Error, fmt};
+use std::{error::Error, fmt, vec::Drain};
 
+mod bitmap;
+mod canvas;
 pub mod controls;
+mod event;
+mod focus;
 mod font;
+mod layout;
+mod nine_slice;
+mod pixel;
+mod rect;
 mod resources;
+mod store;
+mod theme;
 
+pub use bitmap::Bitmap;
 pub use blit::Color;
+pub use canvas::Canvas;
+pub use event::{GuiEvent, GuiEventKind};
+pub use rect::{Alignment, Insets, Rect};
 
 use controls::*;
-pub use font::FontSettings;
+use focus::Direction;
+pub use focus::FocusIndicator;
+pub use font::{FontSettings, GlyphRange};
+pub use layout::Layout;
+pub use nine_slice::NineSlice;
+pub use pixel::PixelBuffer;
 use resources::*;
-pub use resources::{FontRef, SpriteRef};
+pub use resources::{FontRef, NineSliceRef, SpriteRef};
+use store::{Key, Store};
+pub use theme::{Shade, Style, StyleState, Theme};
 
 /// An error type for when a reference is not valid anymore.
 #[derive(Debug, Clone)]
@@ -79,8 +105,36 @@ impl Error for InvalidControlReference {
 }
 
 /// A newtype used to as a reference for controls.
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
-pub struct ControlRef(usize);
+///
+/// References are only valid for the `Gui` which created them and as long as the control is not
+/// removed.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct ControlRef(Key);
+
+/// A registered control and its place in the hierarchy.
+struct Entry {
+    control: Box<dyn Control>,
+    parent: Option<ControlRef>,
+    /// The child controls from back to front.
+    children: Vec<ControlRef>,
+
+    visible: bool,
+    enabled: bool,
+    opacity: f32,
+}
+
+impl Entry {
+    fn new(control: Box<dyn Control>, parent: Option<ControlRef>) -> Self {
+        Entry {
+            control,
+            parent,
+            children: Vec::new(),
+            visible: true,
+            enabled: true,
+            opacity: 1.0,
+        }
+    }
+}
 
 /// The main entry point.
 ///
@@ -89,8 +143,19 @@ pub struct Gui {
     size: (i32, i32),
 
     resources: Resources,
-    controls: Vec<(ControlRef, Box<dyn Control>)>,
-    control_ref: usize,
+    controls: Store<Entry>,
+    /// The top level controls in the order in which they are registered.
+    order: Vec<ControlRef>,
+    /// Positions the top level controls.
+    layout: Option<Layout>,
+    events: Vec<GuiEvent>,
+
+    /// The mouse buttons held down during the previous update.
+    mouse_buttons: MouseButtons,
+
+    /// The control that receives the keyboard input.
+    focused: Option<ControlRef>,
+    focus_indicator: FocusIndicator,
 }
 
 impl Gui {
@@ -99,54 +164,381 @@ impl Gui {
         Gui {
             size,
             resources: Resources::new(),
-            controls: Vec::new(),
-            control_ref: 0,
+            controls: Store::new(),
+            order: Vec::new(),
+            layout: None,
+            events: Vec::new(),
+            mouse_buttons: MouseButtons::default(),
+            focused: None,
+            focus_indicator: FocusIndicator::default(),
         }
     }
 
+    /// The size of the area the GUI is drawn in.
+    pub fn size(&self) -> (i32, i32) {
+        self.size
+    }
+
+    /// Change the size of the area the GUI is drawn in, for example when the window is resized.
+    ///
+    /// The top level controls are positioned again when there's a layout.
+    pub fn set_size(&mut self, size: (i32, i32)) {
+        self.size = size;
+    }
+
+    /// Position the top level controls automatically in the area of the GUI, with `None` the
+    /// controls keep the positions they have.
+    pub fn set_layout(&mut self, layout: Option<Layout>) {
+        self.layout = layout;
+    }
+
     /// Handle the user input and information as supplied by the windowing library.
+    ///
+    /// The controls are updated from front to back, the control in front under the mouse hides
+    /// it from the controls behind it.
+    ///
+    /// Tab and Shift-Tab move the keyboard focus to the next and previous control from top to
+    /// bottom and left to right, the arrow keys move it to the nearest control in that direction
+    /// and Enter or Space activate the focused control. Escape removes the focus from the
+    /// control. Keys that the focused control handles itself, such as the arrow keys of a text
+    /// input, are passed to it instead.
+    ///
+    /// The navigation intents of a gamepad in `ControlState::nav` work the same way as their keys.
+    ///
+    /// Events of the previous update which are not retrieved with `events` are discarded.
     pub fn update(&mut self, state: &ControlState) {
-        for control_tuple in self.controls.iter_mut() {
-            control_tuple.1.update(state, &self.resources);
-        }
+        self.events.clear();
+
+        // Compute which buttons changed since the previous update
+        let mut state = state.clone();
+        let mouse_buttons = state.mouse_buttons();
+        state.mouse_pressed = mouse_buttons.difference(self.mouse_buttons);
+        state.mouse_released = self.mouse_buttons.difference(mouse_buttons);
+        self.mouse_buttons = mouse_buttons;
+
+        // Navigation intents are handled just like the keys
+        let intent_keys: Vec<_> = state
+            .nav
+            .iter()
+            .map(|intent| KeyEvent::Pressed(intent.key()))
+            .collect();
+        state.keys.extend(intent_keys);
+
+        self.layout_controls();
+        self.update_focus(&mut state);
+        update_controls(
+            &mut self.controls,
+            &self.order,
+            &state,
+            &self.resources,
+            &mut self.events,
+        );
+    }
+
+    /// Retrieve the events which happened during the last `update` call.
+    pub fn events(&mut self) -> Drain<'_, GuiEvent> {
+        self.events.drain(..)
     }
 
     /// Draw the drawable GUI controls on a target buffer.
-    pub fn draw_to_buffer(&mut self, buffer: &mut Vec<u32>) {
-        for control_tuple in self.controls.iter_mut() {
-            control_tuple
-                .1
-                .draw(buffer, self.size.0 as usize, &self.resources);
+    ///
+    /// Nothing will be drawn outside of the size of the GUI or the buffer.
+    ///
+    /// A `&mut [u32]` buffer has `0xAARRGGBB` pixels, other formats can be drawn on by passing a
+    /// `PixelBuffer`. The buffer must be as wide as the GUI, use `draw_to_canvas` for buffers with
+    /// another width or stride.
+    pub fn draw_to_buffer<'a, B>(&mut self, buffer: B)
+    where
+        B: Into<PixelBuffer<'a>>,
+    {
+        let mut canvas = self.canvas(buffer.into());
+        self.draw_to_canvas(&mut canvas);
+    }
+
+    /// Draw the drawable GUI controls on a canvas, with the top left corner of the GUI at the
+    /// top left corner of the canvas.
+    ///
+    /// This can draw the GUI in a region of a larger framebuffer, for example a viewport of a
+    /// split-screen game. The mouse position of the `ControlState` is then relative to the
+    /// region. Nothing will be drawn outside of the size of the GUI or the canvas.
+    pub fn draw_to_canvas(&mut self, canvas: &mut Canvas) {
+        self.layout_controls();
+
+        let mut canvas = canvas.region(Rect::from_pos_size((0, 0), self.size));
+        draw_controls(&self.controls, &self.order, &mut canvas, &self.resources);
+
+        if let Some(focused) = self.focused.filter(|&focused| self.can_focus(focused)) {
+            if let Ok(bounds) = self.bounds(focused) {
+                self.focus_indicator
+                    .draw(&mut canvas, &self.resources, bounds);
+            }
         }
     }
 
     /// Draw a label a single frame.
-    pub fn draw_label<S: Into<String>>(
-        &mut self,
-        buffer: &mut Vec<u32>,
-        font_ref: FontRef,
-        string: S,
-        pos: (i32, i32),
-    ) {
+    pub fn draw_label<'a, B, S>(&mut self, buffer: B, font_ref: FontRef, string: S, pos: (i32, i32))
+    where
+        B: Into<PixelBuffer<'a>>,
+        S: Into<String>,
+    {
         let font = self.resources.get_font(font_ref).unwrap();
 
-        font.draw_string(buffer, self.size.0 as usize, string.into(), pos);
+        font.draw_string(&mut self.canvas(buffer.into()), string.into(), pos);
     }
 
     /// Register a control.
     pub fn register<T: 'static + Control>(&mut self, ctrl: T) -> ControlRef {
-        self.control_ref += 1;
+        let control_ref = ControlRef(self.controls.insert(Entry::new(Box::new(ctrl), None)));
+        self.order.push(control_ref);
+
+        control_ref
+    }
 
+    /// Register a control as a child of a container such as a `Panel`.
+    ///
+    /// The position of the control is relative to the container.
+    pub fn register_child<T: 'static + Control>(
+        &mut self,
+        parent: ControlRef,
+        ctrl: T,
+    ) -> Result<ControlRef, InvalidControlReference> {
+        if self.controls.get(parent.0).is_none() {
+            return Err(InvalidControlReference);
+        }
+
+        let mut ctrl: Box<dyn Control> = Box::new(ctrl);
+        if !self.enabled_with_parents(parent) {
+            ctrl.set_enabled(false);
+        }
+
+        let control_ref = ControlRef(self.controls.insert(Entry::new(ctrl, Some(parent))));
         self.controls
-            .push((ControlRef(self.control_ref), Box::new(ctrl)));
+            .get_mut(parent.0)
+            .ok_or(InvalidControlReference)?
+            .children
+            .push(control_ref);
 
-        ControlRef(self.control_ref)
+        Ok(control_ref)
+    }
+
+    /// Show or hide a control.
+    ///
+    /// Hidden controls and their children are not drawn, don't receive input and are skipped by
+    /// layouts.
+    pub fn set_visible(
+        &mut self,
+        control_ref: ControlRef,
+        visible: bool,
+    ) -> Result<(), InvalidControlReference> {
+        self.controls
+            .get_mut(control_ref.0)
+            .ok_or(InvalidControlReference)?
+            .visible = visible;
+
+        Ok(())
+    }
+
+    /// Whether the control is shown, `false` when the reference is not valid.
+    pub fn visible(&self, control_ref: ControlRef) -> bool {
+        self.controls
+            .get(control_ref.0)
+            .is_some_and(|entry| entry.visible)
+    }
+
+    /// Enable or disable a control.
+    ///
+    /// Disabled controls and their children are still drawn, but don't receive input. Controls
+    /// such as buttons are drawn in a distinct style when disabled.
+    pub fn set_enabled(
+        &mut self,
+        control_ref: ControlRef,
+        enabled: bool,
+    ) -> Result<(), InvalidControlReference> {
+        let parent = {
+            let entry = self
+                .controls
+                .get_mut(control_ref.0)
+                .ok_or(InvalidControlReference)?;
+            entry.enabled = enabled;
+
+            entry.parent
+        };
+
+        let parent_enabled = parent.is_none_or(|parent| self.enabled_with_parents(parent));
+        propagate_enabled(&mut self.controls, control_ref, parent_enabled);
+
+        Ok(())
+    }
+
+    /// Whether the control is enabled, `false` when the reference is not valid.
+    ///
+    /// A control which is enabled can still be disabled by one of its containers.
+    pub fn enabled(&self, control_ref: ControlRef) -> bool {
+        self.controls
+            .get(control_ref.0)
+            .is_some_and(|entry| entry.enabled)
+    }
+
+    /// Change how opaque a control and its children are drawn, from `0.0`, invisible, to `1.0`.
+    ///
+    /// The control is blended together with its children, so a panel can be faded in and out.
+    /// Unlike hidden controls, transparent controls still receive input.
+    pub fn set_opacity(
+        &mut self,
+        control_ref: ControlRef,
+        opacity: f32,
+    ) -> Result<(), InvalidControlReference> {
+        self.controls
+            .get_mut(control_ref.0)
+            .ok_or(InvalidControlReference)?
+            .opacity = opacity.clamp(0.0, 1.0);
+
+        Ok(())
+    }
+
+    /// How opaque the control is drawn, `0.0` when the reference is not valid.
+    pub fn opacity(&self, control_ref: ControlRef) -> f32 {
+        self.controls
+            .get(control_ref.0)
+            .map_or(0.0, |entry| entry.opacity)
+    }
+
+    /// The control that receives the keyboard input.
+    pub fn focused(&self) -> Option<ControlRef> {
+        self.focused
+    }
+
+    /// Move the keyboard focus to a control, or remove it with `None`.
+    ///
+    /// Controls which can't receive the focus, such as labels, are ignored.
+    pub fn set_focused(
+        &mut self,
+        control_ref: Option<ControlRef>,
+    ) -> Result<(), InvalidControlReference> {
+        match control_ref {
+            Some(control_ref) if self.controls.get(control_ref.0).is_none() => {
+                Err(InvalidControlReference)
+            }
+            Some(control_ref) if !self.can_focus(control_ref) => Ok(()),
+            _ => {
+                self.change_focus(control_ref);
+
+                Ok(())
+            }
+        }
+    }
+
+    /// Move the keyboard focus to the next control from top to bottom and left to right, as if
+    /// Tab is pressed.
+    pub fn focus_next(&mut self) {
+        self.cycle_focus(true);
+    }
+
+    /// Move the keyboard focus to the previous control, as if Shift-Tab is pressed.
+    pub fn focus_previous(&mut self) {
+        self.cycle_focus(false);
+    }
+
+    /// Change how the focused control is marked, by default it's a yellow outline.
+    pub fn set_focus_indicator(&mut self, focus_indicator: FocusIndicator) {
+        self.focus_indicator = focus_indicator;
+    }
+
+    /// Change the look of all controls.
+    pub fn set_theme(&mut self, theme: Theme) {
+        *self.resources.theme_mut() = theme;
+    }
+
+    /// The theme used to draw the controls.
+    pub fn theme(&self) -> &Theme {
+        self.resources.theme()
+    }
+
+    /// The theme used to draw the controls, to restyle them at runtime.
+    pub fn theme_mut(&mut self) -> &mut Theme {
+        self.resources.theme_mut()
+    }
+
+    /// Move a control in front of the other controls in the same container.
+    pub fn raise(&mut self, control_ref: ControlRef) -> Result<(), InvalidControlReference> {
+        let siblings = self.siblings_mut(control_ref)?;
+        siblings.retain(|&c| c != control_ref);
+        siblings.push(control_ref);
+
+        Ok(())
+    }
+
+    /// Move a control behind the other controls in the same container.
+    pub fn lower(&mut self, control_ref: ControlRef) -> Result<(), InvalidControlReference> {
+        let siblings = self.siblings_mut(control_ref)?;
+        siblings.retain(|&c| c != control_ref);
+        siblings.insert(0, control_ref);
+
+        Ok(())
+    }
+
+    /// The control in front at a position that hides it from the controls behind it.
+    pub fn control_at(&self, pos: (i32, i32)) -> Option<ControlRef> {
+        control_at(&self.controls, &self.order, pos, &self.resources)
+    }
+
+    /// The container the control is registered in, `None` for top level controls.
+    pub fn parent(&self, control_ref: ControlRef) -> Option<ControlRef> {
+        self.controls
+            .get(control_ref.0)
+            .and_then(|entry| entry.parent)
+    }
+
+    /// The controls registered in a container, in the order in which they are registered.
+    pub fn children(&self, control_ref: ControlRef) -> &[ControlRef] {
+        self.controls
+            .get(control_ref.0)
+            .map_or(&[], |entry| &entry.children[..])
+    }
+
+    /// The area a control covers in the GUI, including the offsets of the containers it's in.
+    pub fn bounds(&self, control_ref: ControlRef) -> Result<Rect, InvalidControlReference> {
+        let entry = self
+            .controls
+            .get(control_ref.0)
+            .ok_or(InvalidControlReference)?;
+        let mut bounds = entry.control.bounds(&self.resources);
+
+        let mut parent = entry.parent;
+        while let Some(parent_ref) = parent {
+            let entry = self
+                .controls
+                .get(parent_ref.0)
+                .ok_or(InvalidControlReference)?;
+
+            // Controls without a content area place their children relative to their position
+            let origin = entry
+                .control
+                .content_area(&self.resources)
+                .map_or_else(|| entry.control.pos(), |area| area.pos());
+            bounds = bounds.translate(origin.0, origin.1);
+
+            parent = entry.parent;
+        }
+
+        Ok(bounds)
+    }
+
+    /// The size a control needs to fit its content.
+    pub fn preferred_size(
+        &self,
+        control_ref: ControlRef,
+    ) -> Result<(i32, i32), InvalidControlReference> {
+        self.controls
+            .get(control_ref.0)
+            .map(|entry| entry.control.preferred_size(&self.resources))
+            .ok_or(InvalidControlReference)
     }
 
     /// Retrieve a control by reference.
     pub fn get<T: 'static + Control>(&self, control_ref: ControlRef) -> Result<&T, Box<dyn Error>> {
-        match self.controls.iter().find(|&c| c.0 == control_ref) {
-            Some(c) => match c.1.as_any().downcast_ref::<T>() {
+        match self.controls.get(control_ref.0) {
+            Some(entry) => match entry.control.as_any().downcast_ref::<T>() {
                 Some(obj) => Ok(obj),
                 None => Err(Box::new(InvalidControlReference)),
             },
@@ -159,8 +551,8 @@ impl Gui {
         &mut self,
         control_ref: ControlRef,
     ) -> Result<&mut T, Box<dyn Error>> {
-        match self.controls.iter_mut().find(|c| c.0 == control_ref) {
-            Some(c) => match c.1.as_any_mut().downcast_mut::<T>() {
+        match self.controls.get_mut(control_ref.0) {
+            Some(entry) => match entry.control.as_any_mut().downcast_mut::<T>() {
                 Some(obj) => Ok(obj),
                 None => Err(Box::new(InvalidControlReference)),
             },
@@ -168,6 +560,73 @@ impl Gui {
         }
     }
 
+    /// Remove a control, the reference and any copies of it will be invalid afterwards.
+    ///
+    /// When the control is a container its children are removed as well.
+    pub fn unregister(
+        &mut self,
+        control_ref: ControlRef,
+    ) -> Result<Box<dyn Control>, InvalidControlReference> {
+        let entry = self
+            .controls
+            .remove(control_ref.0)
+            .ok_or(InvalidControlReference)?;
+
+        match entry
+            .parent
+            .and_then(|parent| self.controls.get_mut(parent.0))
+        {
+            Some(parent) => parent.children.retain(|&c| c != control_ref),
+            None => self.order.retain(|&c| c != control_ref),
+        }
+
+        // Remove all descendants
+        let mut children = entry.children;
+        while let Some(child) = children.pop() {
+            if let Some(child) = self.controls.remove(child.0) {
+                children.extend(child.children);
+            }
+        }
+
+        // The control or one of its descendants might have had the focus
+        let mut control = entry.control;
+        if self.focused == Some(control_ref) {
+            control.set_focused(false);
+        }
+        if let Some(focused) = self.focused {
+            if self.controls.get(focused.0).is_none() {
+                self.focused = None;
+            }
+        }
+
+        Ok(control)
+    }
+
+    /// Remove a control and return it as the concrete type.
+    ///
+    /// When the control is not of type `T` it will not be removed.
+    pub fn take<T: 'static + Control>(
+        &mut self,
+        control_ref: ControlRef,
+    ) -> Result<T, Box<dyn Error>> {
+        match self.controls.get(control_ref.0) {
+            Some(entry) if entry.control.as_any().is::<T>() => {
+                match self.unregister(control_ref)?.into_any().downcast::<T>() {
+                    Ok(obj) => Ok(*obj),
+                    Err(_) => Err(Box::new(InvalidControlReference)),
+                }
+            }
+            _ => Err(Box::new(InvalidControlReference)),
+        }
+    }
+
+    /// Remove all controls, all references will be invalid afterwards.
+    pub fn clear(&mut self) {
+        self.controls.clear();
+        self.order.clear();
+        self.focused = None;
+    }
+
     /// Return the default font loaded from the `assets/` folder and parsed by `build.rs`. Which is
     /// always the first item added to the fonts array.
     pub fn default_font(&self) -> FontRef {
@@ -179,7 +638,7 @@ impl Gui {
     /// This function is only available when the `"file-loading"` feature is enabled.
     ///
     /// The mask color is the color that will be used as alpha in the sprite, a common color to use
-    /// for this is `0xFF00FF`.
+    /// for this is `0xFF00FF`. PNG images can also have an alpha channel for translucent pixels.
     ///
     /// Returns a reference to the image.
     #[cfg(feature = "file-loading")]
@@ -199,6 +658,12 @@ impl Gui {
         self.resources.load_sprite_from_memory(buffer)
     }
 
+    /// Add a sprite from a bitmap, for example one with translucent pixels. Returns a reference
+    /// to the image.
+    pub fn load_sprite_from_bitmap(&mut self, bitmap: Bitmap) -> SpriteRef {
+        self.resources.load_sprite_from_bitmap(bitmap)
+    }
+
     /// Load font image from a path.
     ///
     /// This function is only available when the `"file-loading"` feature is enabled.
@@ -228,4 +693,710 @@ impl Gui {
         self.resources
             .load_font_sprite_from_memory(buffer, settings)
     }
+
+    /// Load nine-slice image from a path.
+    ///
+    /// This function is only available when the `"file-loading"` feature is enabled.
+    ///
+    /// The mask color is the color that will be used as alpha in the sprite, a common color to use
+    /// for this is `0xFF00FF`. The insets define the size of the borders which are not stretched.
+    ///
+    /// Returns a reference to the nine-slice sprite.
+    #[cfg(feature = "file-loading")]
+    pub fn load_nine_slice_from_file<P>(
+        &mut self,
+        path: P,
+        mask_color: Color,
+        insets: Insets,
+    ) -> Result<NineSliceRef, Box<dyn Error>>
+    where
+        P: AsRef<Path>,
+    {
+        self.resources
+            .load_nine_slice_from_file(path, mask_color, insets)
+    }
+
+    /// Load nine-slice image from serialized memory. Returns a reference to the nine-slice sprite.
+    pub fn load_nine_slice_from_memory(
+        &mut self,
+        buffer: &[u8],
+        insets: Insets,
+    ) -> Result<NineSliceRef, Box<dyn Error>> {
+        self.resources.load_nine_slice_from_memory(buffer, insets)
+    }
+
+    /// The controls in the same container as the control, including the control itself, from
+    /// back to front.
+    fn siblings_mut(
+        &mut self,
+        control_ref: ControlRef,
+    ) -> Result<&mut Vec<ControlRef>, InvalidControlReference> {
+        let parent = self
+            .controls
+            .get(control_ref.0)
+            .ok_or(InvalidControlReference)?
+            .parent;
+
+        match parent {
+            Some(parent) => self
+                .controls
+                .get_mut(parent.0)
+                .map(|entry| &mut entry.children)
+                .ok_or(InvalidControlReference),
+            None => Ok(&mut self.order),
+        }
+    }
+
+    /// Whether the control and all of its containers are enabled.
+    fn enabled_with_parents(&self, control_ref: ControlRef) -> bool {
+        let mut current = Some(control_ref);
+        while let Some(control_ref) = current {
+            match self.controls.get(control_ref.0) {
+                Some(entry) if entry.enabled => current = entry.parent,
+                _ => return false,
+            }
+        }
+
+        true
+    }
+
+    /// Whether the control can receive the focus and it and its containers are visible and
+    /// enabled.
+    fn can_focus(&self, control_ref: ControlRef) -> bool {
+        match self.controls.get(control_ref.0) {
+            Some(entry) if entry.control.focusable() => (),
+            _ => return false,
+        }
+
+        let mut current = Some(control_ref);
+        while let Some(control_ref) = current {
+            match self.controls.get(control_ref.0) {
+                Some(entry) if entry.visible && entry.enabled => current = entry.parent,
+                _ => return false,
+            }
+        }
+
+        true
+    }
+
+    /// Tell the previously and the newly focused controls that the focus changed.
+    fn change_focus(&mut self, control_ref: Option<ControlRef>) {
+        if self.focused == control_ref {
+            return;
+        }
+
+        if let Some(entry) = self
+            .focused
+            .and_then(|focused| self.controls.get_mut(focused.0))
+        {
+            entry.control.set_focused(false);
+        }
+        if let Some(entry) =
+            control_ref.and_then(|control_ref| self.controls.get_mut(control_ref.0))
+        {
+            entry.control.set_focused(true);
+        }
+
+        self.focused = control_ref;
+    }
+
+    /// All controls that can receive the focus with their areas in the GUI.
+    fn focus_candidates(&self) -> Vec<(ControlRef, Rect)> {
+        let mut candidates = Vec::new();
+        focus_candidates(
+            &self.controls,
+            &self.order,
+            (0, 0),
+            &self.resources,
+            &mut candidates,
+        );
+
+        candidates
+    }
+
+    /// Move the focus forward or backward in reading order, wrapping around at the ends.
+    fn cycle_focus(&mut self, forward: bool) {
+        let mut candidates = self.focus_candidates();
+        if candidates.is_empty() {
+            return;
+        }
+        focus::sort_reading_order(&mut candidates);
+
+        let len = candidates.len();
+        let index = match self
+            .focused
+            .and_then(|focused| candidates.iter().position(|&(c, _)| c == focused))
+        {
+            Some(index) if forward => (index + 1) % len,
+            Some(index) => (index + len - 1) % len,
+            None if forward => 0,
+            None => len - 1,
+        };
+
+        self.change_focus(Some(candidates[index].0));
+    }
+
+    /// Move the focus to the nearest control in a direction, returns whether it moved.
+    ///
+    /// Without a focused control the first control in reading order is focused.
+    fn move_focus(&mut self, direction: Direction) -> bool {
+        let mut candidates = self.focus_candidates();
+        let from = match self.focused.and_then(|focused| {
+            candidates
+                .iter()
+                .find(|&&(c, _)| c == focused)
+                .map(|&(_, rect)| rect)
+        }) {
+            Some(from) => from,
+            None => {
+                focus::sort_reading_order(&mut candidates);
+                let first = candidates.first().map(|&(c, _)| c);
+                self.change_focus(first);
+
+                return first.is_some();
+            }
+        };
+
+        match focus::nearest(from, direction, &candidates) {
+            Some(nearest) => {
+                self.change_focus(Some(nearest));
+
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Activate the focused control, returns whether there is one.
+    fn activate_focused(&mut self) -> bool {
+        let focused = match self.focused {
+            Some(focused) => focused,
+            None => return false,
+        };
+
+        let mut kinds = Vec::new();
+        if let Some(entry) = self.controls.get_mut(focused.0) {
+            entry.control.activate(&mut kinds);
+        }
+        self.events.extend(kinds.into_iter().map(|kind| GuiEvent {
+            control: focused,
+            kind,
+        }));
+
+        true
+    }
+
+    /// Move the focus with the mouse and the keyboard.
+    ///
+    /// The keys that are used are removed from the state so the controls don't handle them
+    /// again.
+    fn update_focus(&mut self, state: &mut ControlState) {
+        // The focused control might have been hidden, disabled or removed
+        if let Some(focused) = self.focused {
+            if !self.can_focus(focused) {
+                self.change_focus(None);
+            }
+        }
+
+        // Clicking focuses the control under the mouse, or nothing
+        if state.mouse_just_pressed(MouseButton::Left) {
+            let clicked = self
+                .control_at(state.mouse_pos)
+                .filter(|&control_ref| self.can_focus(control_ref));
+            self.change_focus(clicked);
+        }
+
+        let keys = std::mem::take(&mut state.keys);
+        for key_event in keys {
+            let key = match key_event {
+                KeyEvent::Pressed(key) => key,
+                KeyEvent::Released(_) => {
+                    state.keys.push(key_event);
+                    continue;
+                }
+            };
+
+            let captured = self
+                .focused
+                .and_then(|focused| self.controls.get(focused.0))
+                .is_some_and(|entry| entry.control.captures_key(key));
+
+            let used = !captured
+                && match key {
+                    controls::Key::Tab => {
+                        self.cycle_focus(!state.modifiers.shift);
+
+                        true
+                    }
+                    controls::Key::Up => self.move_focus(Direction::Up),
+                    controls::Key::Down => self.move_focus(Direction::Down),
+                    controls::Key::Left => self.move_focus(Direction::Left),
+                    controls::Key::Right => self.move_focus(Direction::Right),
+                    controls::Key::Enter | controls::Key::Space => self.activate_focused(),
+                    controls::Key::Escape => {
+                        let focused = self.focused.is_some();
+                        self.change_focus(None);
+
+                        focused
+                    }
+                    _ => false,
+                };
+
+            if !used {
+                state.keys.push(key_event);
+            }
+        }
+    }
+
+    /// Position all controls which are in a layout.
+    fn layout_controls(&mut self) {
+        layout_controls(
+            &mut self.controls,
+            &self.order,
+            self.layout,
+            self.size,
+            &self.resources,
+        );
+    }
+
+    /// Create a canvas with the size of the GUI for a buffer.
+    fn canvas<'a>(&self, buffer: PixelBuffer<'a>) -> Canvas<'a> {
+        Canvas::new(
+            buffer,
+            self.size.0.max(0) as usize,
+            self.size.1.max(0) as usize,
+        )
+    }
+}
+
+/// Update the controls and their children recursively, from front to back.
+///
+/// Returns whether one of the controls is under the mouse and hides it from the controls behind.
+fn update_controls(
+    controls: &mut Store<Entry>,
+    control_refs: &[ControlRef],
+    state: &ControlState,
+    res: &Resources,
+    events: &mut Vec<GuiEvent>,
+) -> bool {
+    let mut state = state.clone();
+    let occluded = state.mouse_occluded;
+    let mut consumed = false;
+
+    let mut kinds = Vec::new();
+    for &control_ref in control_refs.iter().rev() {
+        let (children, area, enabled) = match controls.get(control_ref.0) {
+            Some(entry) if entry.visible => (
+                entry.children.clone(),
+                entry.control.content_area(res),
+                entry.enabled,
+            ),
+            _ => continue,
+        };
+
+        // The children are in front of their container, disabled containers disable them too
+        if let (true, false, Some(area)) = (enabled, children.is_empty(), area) {
+            // The children see the mouse relative to the container
+            let mut child_state = state.clone();
+            child_state.mouse_pos = (state.mouse_pos.0 - area.x, state.mouse_pos.1 - area.y);
+            child_state.mouse_occluded |= !area.contains(state.mouse_pos);
+
+            consumed |= update_controls(controls, &children, &child_state, res, events);
+            state.mouse_occluded = occluded || consumed;
+        }
+
+        let entry = match controls.get_mut(control_ref.0) {
+            Some(entry) => entry,
+            None => continue,
+        };
+
+        if enabled {
+            entry.control.update(&state, res, &mut kinds);
+            events.extend(kinds.drain(..).map(|kind| GuiEvent {
+                control: control_ref,
+                kind,
+            }));
+        }
+
+        if !state.mouse_occluded
+            && entry.control.blocks_mouse()
+            && entry.control.bounds(res).contains(state.mouse_pos)
+        {
+            consumed = true;
+            state.mouse_occluded = true;
+        }
+    }
+
+    consumed
+}
+
+/// Tell the control and its children recursively whether they are enabled.
+fn propagate_enabled(controls: &mut Store<Entry>, control_ref: ControlRef, parent_enabled: bool) {
+    let (children, enabled) = match controls.get_mut(control_ref.0) {
+        Some(entry) => {
+            let enabled = parent_enabled && entry.enabled;
+            entry.control.set_enabled(enabled);
+
+            (entry.children.clone(), enabled)
+        }
+        None => return,
+    };
+
+    for child in children {
+        propagate_enabled(controls, child, enabled);
+    }
+}
+
+/// Collect the controls that can receive the focus recursively, with their areas moved by the
+/// offset of their containers.
+fn focus_candidates(
+    controls: &Store<Entry>,
+    control_refs: &[ControlRef],
+    origin: (i32, i32),
+    res: &Resources,
+    candidates: &mut Vec<(ControlRef, Rect)>,
+) {
+    for &control_ref in control_refs.iter() {
+        let entry = match controls.get(control_ref.0) {
+            Some(entry) if entry.visible && entry.enabled => entry,
+            _ => continue,
+        };
+
+        if entry.control.focusable() {
+            candidates.push((
+                control_ref,
+                entry.control.bounds(res).translate(origin.0, origin.1),
+            ));
+        }
+
+        if let Some(area) = entry.control.content_area(res) {
+            let origin = (origin.0 + area.x, origin.1 + area.y);
+            focus_candidates(controls, &entry.children, origin, res, candidates);
+        }
+    }
+}
+
+/// Find the control in front at a position recursively.
+fn control_at(
+    controls: &Store<Entry>,
+    control_refs: &[ControlRef],
+    pos: (i32, i32),
+    res: &Resources,
+) -> Option<ControlRef> {
+    for control_ref in control_refs.iter().rev() {
+        let entry = match controls.get(control_ref.0) {
+            Some(entry) if entry.visible => entry,
+            _ => continue,
+        };
+
+        if let Some(area) = entry.control.content_area(res) {
+            if area.contains(pos) {
+                let local = (pos.0 - area.x, pos.1 - area.y);
+                if let Some(child) = control_at(controls, &entry.children, local, res) {
+                    return Some(child);
+                }
+            }
+        }
+
+        if entry.control.blocks_mouse() && entry.control.bounds(res).contains(pos) {
+            return Some(*control_ref);
+        }
+    }
+
+    None
+}
+
+/// Position the controls and their children recursively.
+fn layout_controls(
+    controls: &mut Store<Entry>,
+    control_refs: &[ControlRef],
+    layout: Option<Layout>,
+    area: (i32, i32),
+    res: &Resources,
+) {
+    if let Some(layout) = layout {
+        // Hidden controls don't take up space
+        let visible: Vec<_> = control_refs
+            .iter()
+            .filter(|control_ref| {
+                controls
+                    .get(control_ref.0)
+                    .is_some_and(|entry| entry.visible)
+            })
+            .collect();
+        let sizes: Vec<_> = visible
+            .iter()
+            .map(|control_ref| {
+                controls
+                    .get(control_ref.0)
+                    .map_or((0, 0), |entry| entry.control.size(res))
+            })
+            .collect();
+
+        for (control_ref, pos) in visible.into_iter().zip(layout.arrange(area, &sizes)) {
+            if let Some(entry) = controls.get_mut(control_ref.0) {
+                entry.control.set_pos(pos.0, pos.1);
+            }
+        }
+    }
+
+    for control_ref in control_refs.iter() {
+        let (children, layout, area) = match controls.get(control_ref.0) {
+            Some(entry) if entry.visible && !entry.children.is_empty() => {
+                match entry.control.content_area(res) {
+                    Some(area) => (entry.children.clone(), entry.control.layout(), area),
+                    None => continue,
+                }
+            }
+            _ => continue,
+        };
+
+        layout_controls(controls, &children, layout, area.size(), res);
+    }
+}
+
+/// Draw the controls and their children recursively.
+fn draw_controls(
+    controls: &Store<Entry>,
+    control_refs: &[ControlRef],
+    canvas: &mut Canvas,
+    res: &Resources,
+) {
+    for control_ref in control_refs.iter() {
+        let entry = match controls.get(control_ref.0) {
+            Some(entry) if entry.visible => entry,
+            _ => continue,
+        };
+
+        if entry.opacity < 1.0 {
+            // Blend the control with its children so they don't show through each other
+            canvas.draw_layer(entry.control.bounds(res), entry.opacity, |canvas| {
+                draw_entry(controls, entry, canvas, res)
+            });
+        } else {
+            draw_entry(controls, entry, canvas, res);
+        }
+    }
+}
+
+/// Draw a control and its children.
+fn draw_entry(controls: &Store<Entry>, entry: &Entry, canvas: &mut Canvas, res: &Resources) {
+    entry.control.draw(canvas, res);
+
+    if entry.children.is_empty() {
+        return;
+    }
+    if let Some(area) = entry.control.content_area(res) {
+        draw_controls(controls, &entry.children, &mut canvas.region(area), res);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::controls::*;
+    use super::*;
+
+    /// The state of a frame in which keys are pressed.
+    fn press(keys: &[controls::Key]) -> ControlState {
+        ControlState {
+            keys: keys.iter().cloned().map(KeyEvent::Pressed).collect(),
+            ..ControlState::default()
+        }
+    }
+
+    /// The state of a frame in which the left mouse button is held down.
+    fn click(pos: (i32, i32)) -> ControlState {
+        ControlState {
+            mouse_pos: pos,
+            mouse_down: true,
+            ..ControlState::default()
+        }
+    }
+
+    #[test]
+    fn click_focuses_text_input() {
+        let mut gui = Gui::new((100, 100));
+        let font = gui.default_font();
+        let first = gui.register(TextInput::new(font, 50));
+        let second = gui.register(TextInput::new(font, 50).with_pos(0, 50));
+
+        gui.set_focused(Some(first)).unwrap();
+        gui.update(&click((10, 55)));
+        assert_eq!(gui.focused(), Some(second));
+        assert!(!gui.get::<TextInput>(first).unwrap().focused());
+        assert!(gui.get::<TextInput>(second).unwrap().focused());
+
+        // Only the focused input receives the typed text
+        gui.update(&ControlState {
+            text: "a".into(),
+            ..ControlState::default()
+        });
+        assert_eq!(gui.get::<TextInput>(first).unwrap().text(), "");
+        assert_eq!(gui.get::<TextInput>(second).unwrap().text(), "a");
+
+        // Clicking next to the inputs removes the focus
+        gui.update(&click((90, 90)));
+        assert_eq!(gui.focused(), None);
+        assert!(!gui.get::<TextInput>(second).unwrap().focused());
+    }
+
+    #[test]
+    fn click_focuses_slider() {
+        let mut gui = Gui::new((100, 100));
+        let font = gui.default_font();
+        let input = gui.register(TextInput::new(font, 50));
+        let slider = gui.register(Slider::new((100, 10), Color::from_u32(0)).with_pos(0, 50));
+
+        gui.set_focused(Some(input)).unwrap();
+        gui.update(&click((50, 55)));
+        assert_eq!(gui.focused(), Some(slider));
+        assert!(!gui.get::<TextInput>(input).unwrap().focused());
+        assert!(gui.get::<Slider<Flat>>(slider).unwrap().focused());
+
+        // The arrow keys only change the slider
+        gui.update(&ControlState::default());
+        let value = gui.get::<Slider<Flat>>(slider).unwrap().value();
+        gui.update(&press(&[controls::Key::Left]));
+        assert!(gui.get::<Slider<Flat>>(slider).unwrap().value() < value);
+        assert_eq!(gui.focused(), Some(slider));
+    }
+
+    /// Register 4 buttons in a square, in reading order.
+    fn grid_of_buttons(gui: &mut Gui) -> [ControlRef; 4] {
+        let mut button =
+            |x, y| gui.register(Button::new((20, 20), Color::from_u32(0)).with_pos(x, y));
+
+        // Registered out of order so the order of the focus doesn't follow the registration, the
+        // mouse at the top left corner doesn't hover over them
+        let bottom_right = button(60, 60);
+        let top_left = button(10, 10);
+        let bottom_left = button(10, 60);
+        let top_right = button(60, 10);
+
+        [top_left, top_right, bottom_left, bottom_right]
+    }
+
+    #[test]
+    fn tab_cycles_focus() {
+        let mut gui = Gui::new((100, 100));
+        let buttons = grid_of_buttons(&mut gui);
+
+        for &button in buttons.iter().chain(buttons.iter().take(1)) {
+            gui.update(&press(&[controls::Key::Tab]));
+            assert_eq!(gui.focused(), Some(button));
+        }
+
+        // Shift-tab goes back and wraps around to the last control
+        let shift_tab = ControlState {
+            modifiers: Modifiers {
+                shift: true,
+                ..Modifiers::default()
+            },
+            ..press(&[controls::Key::Tab])
+        };
+        gui.update(&shift_tab);
+        assert_eq!(gui.focused(), Some(buttons[3]));
+        gui.update(&shift_tab);
+        assert_eq!(gui.focused(), Some(buttons[2]));
+    }
+
+    #[test]
+    fn arrows_move_focus_to_nearest() {
+        let mut gui = Gui::new((100, 100));
+        let [top_left, top_right, bottom_left, bottom_right] = grid_of_buttons(&mut gui);
+
+        // Without a focused control the first one is focused
+        gui.update(&press(&[controls::Key::Down]));
+        assert_eq!(gui.focused(), Some(top_left));
+
+        let moves = [
+            (controls::Key::Right, top_right),
+            (controls::Key::Down, bottom_right),
+            (controls::Key::Left, bottom_left),
+            (controls::Key::Up, top_left),
+            // There is nothing above, the focus stays
+            (controls::Key::Up, top_left),
+        ];
+        for &(key, button) in moves.iter() {
+            gui.update(&press(&[key]));
+            assert_eq!(gui.focused(), Some(button));
+        }
+    }
+
+    #[test]
+    fn enter_and_space_activate_focused() {
+        let mut gui = Gui::new((100, 100));
+        let [_, top_right, ..] = grid_of_buttons(&mut gui);
+
+        // Nothing is activated without a focused control
+        gui.update(&press(&[controls::Key::Enter, controls::Key::Space]));
+        assert_eq!(gui.events().count(), 0);
+
+        gui.set_focused(Some(top_right)).unwrap();
+        for &key in [controls::Key::Enter, controls::Key::Space].iter() {
+            gui.update(&press(&[key]));
+            let events: Vec<_> = gui.events().collect();
+            assert_eq!(events.len(), 1);
+            assert_eq!(events[0].control, top_right);
+            assert_eq!(events[0].kind, GuiEventKind::Clicked);
+        }
+    }
+
+    #[test]
+    fn escape_removes_focus() {
+        let mut gui = Gui::new((100, 100));
+        let font = gui.default_font();
+        let input = gui.register(TextInput::new(font, 50));
+
+        gui.set_focused(Some(input)).unwrap();
+        gui.update(&press(&[controls::Key::Escape]));
+        assert_eq!(gui.focused(), None);
+        assert!(!gui.get::<TextInput>(input).unwrap().focused());
+
+        // The input doesn't receive keys anymore
+        gui.update(&ControlState {
+            text: "a".into(),
+            ..press(&[controls::Key::Enter])
+        });
+        assert_eq!(gui.events().count(), 0);
+        assert_eq!(gui.get::<TextInput>(input).unwrap().text(), "");
+    }
+
+    #[test]
+    fn cancel_removes_focus() {
+        let mut gui = Gui::new((100, 100));
+        let button = gui.register(Button::new((10, 10), Color::from_u32(0)));
+
+        gui.set_focused(Some(button)).unwrap();
+        gui.update(&ControlState {
+            nav: vec![NavIntent::Cancel],
+            ..ControlState::default()
+        });
+        assert_eq!(gui.focused(), None);
+
+        // Accepting doesn't click the button anymore
+        gui.update(&ControlState {
+            nav: vec![NavIntent::Accept],
+            ..ControlState::default()
+        });
+        assert_eq!(gui.events().count(), 0);
+    }
+
+    #[test]
+    fn unregister_removes_focus() {
+        let mut gui = Gui::new((100, 100));
+        let font = gui.default_font();
+        let panel = gui.register(Panel::new((100, 100)));
+        let input = gui.register_child(panel, TextInput::new(font, 50)).unwrap();
+
+        gui.set_focused(Some(input)).unwrap();
+        gui.unregister(panel).unwrap();
+        assert_eq!(gui.focused(), None);
+
+        let input = gui.register(TextInput::new(font, 50));
+        gui.set_focused(Some(input)).unwrap();
+        let input = gui.take::<TextInput>(input).unwrap();
+        assert_eq!(gui.focused(), None);
+        assert!(!input.focused());
+    }
 }
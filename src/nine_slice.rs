@@ -0,0 +1,81 @@
+use blit::Color;
+
+use super::bitmap::Bitmap;
+use super::canvas::Canvas;
+use super::rect::{Insets, Rect};
+
+/// A sprite which can be drawn at any size without distorting its borders.
+///
+/// The sprite is divided into 9 parts by the insets. The corners are drawn as is, the edges are
+/// repeated along their side and the center is repeated in both directions.
+///
+/// ```compile_fail
+/// +---+-------+---+
+/// |   |  top  |   |
+/// +---+-------+---+
+/// | l |center | r |
+/// +---+-------+---+
+/// |   |bottom |   |
+/// +---+-------+---+
+/// ```
+#[derive(Debug)]
+pub struct NineSlice {
+    buffer: Bitmap,
+    insets: Insets,
+}
+
+impl NineSlice {
+    pub fn new<B: Into<Bitmap>>(buffer: B, insets: Insets) -> Self {
+        NineSlice {
+            buffer: buffer.into(),
+            insets,
+        }
+    }
+
+    /// The size of the sprite.
+    pub fn size(&self) -> (i32, i32) {
+        self.buffer.size()
+    }
+
+    /// The size of the borders.
+    pub fn insets(&self) -> Insets {
+        self.insets
+    }
+
+    /// Draw the whole sprite in the rectangle, the edges and the center are repeated to fill it.
+    pub fn draw(&self, canvas: &mut Canvas, rect: Rect) {
+        self.draw_row(canvas, 0, 1, rect);
+    }
+
+    /// Draw a part of a sprite which is divided vertically into `rows` parts of equal height in
+    /// the rectangle, every part uses the same insets.
+    pub fn draw_row(&self, canvas: &mut Canvas, row: i32, rows: i32, rect: Rect) {
+        canvas.blit_nine_slice(&self.buffer, self.row_rect(row, rows), self.insets, rect);
+    }
+
+    /// Draw a part of a sprite like `draw_row` with every pixel multiplied by a color.
+    pub fn draw_row_tinted(
+        &self,
+        canvas: &mut Canvas,
+        row: i32,
+        rows: i32,
+        rect: Rect,
+        tint: Color,
+    ) {
+        canvas.blit_nine_slice_tinted(
+            &self.buffer,
+            self.row_rect(row, rows),
+            self.insets,
+            rect,
+            tint,
+        );
+    }
+
+    /// The area of a part of the sprite which is divided vertically into `rows` parts.
+    fn row_rect(&self, row: i32, rows: i32) -> Rect {
+        let (width, height) = self.buffer.size();
+        let row_height = height / rows.max(1);
+
+        Rect::new(0, row * row_height, width, row_height)
+    }
+}
@@ -0,0 +1,196 @@
+use std::ops::Range;
+
+/// A buffer the GUI draws on, the variant is the format of its pixels.
+///
+/// The pixels are converted while they are drawn, so the buffer can be passed to a window or
+/// uploaded as a texture without converting it afterwards. A `&mut [u32]` or `&mut Vec<u32>`
+/// converts into an `Argb32` buffer.
+///
+/// ```rust
+/// use direct_gui::*;
+///
+/// let mut gui = Gui::new((320, 240));
+///
+/// // Draw on a buffer for a handheld with a 16-bit display
+/// let mut screen = vec![0u16; 320 * 240];
+/// gui.draw_to_buffer(PixelBuffer::Rgb565(&mut screen));
+/// ```
+#[derive(Debug)]
+pub enum PixelBuffer<'a> {
+    /// The value of every pixel is `0xAARRGGBB`, as `minifb` uses.
+    Argb32(&'a mut [u32]),
+    /// Every pixel has the bytes red, green, blue and alpha in this order in memory.
+    Rgba32(&'a mut [u32]),
+    /// Every pixel has the bytes blue, green, red and alpha in this order in memory.
+    Bgra32(&'a mut [u32]),
+    /// The value of every pixel has 5 bits red, 6 bits green and 5 bits blue:
+    /// `0bRRRRR_GGGGGG_BBBBB`.
+    Rgb565(&'a mut [u16]),
+}
+
+impl<'a> PixelBuffer<'a> {
+    /// The amount of pixels in the buffer.
+    pub fn len(&self) -> usize {
+        match self {
+            PixelBuffer::Argb32(buffer)
+            | PixelBuffer::Rgba32(buffer)
+            | PixelBuffer::Bgra32(buffer) => buffer.len(),
+            PixelBuffer::Rgb565(buffer) => buffer.len(),
+        }
+    }
+
+    /// Whether the buffer has no pixels.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The pixel at an index as `0xAARRGGBB`.
+    pub fn get(&self, index: usize) -> u32 {
+        match self {
+            PixelBuffer::Argb32(buffer) => buffer[index],
+            PixelBuffer::Rgba32(buffer) => {
+                let [r, g, b, a] = buffer[index].to_ne_bytes();
+
+                u32::from_be_bytes([a, r, g, b])
+            }
+            PixelBuffer::Bgra32(buffer) => u32::from_le_bytes(buffer[index].to_ne_bytes()),
+            PixelBuffer::Rgb565(buffer) => {
+                let pixel = u32::from(buffer[index]);
+                let (r, g, b) = ((pixel >> 11) & 0x1F, (pixel >> 5) & 0x3F, pixel & 0x1F);
+
+                // Repeat the highest bits in the lowest bits so white stays white
+                0xFF_00_00_00
+                    | ((r << 3 | r >> 2) << 16)
+                    | ((g << 2 | g >> 4) << 8)
+                    | (b << 3 | b >> 2)
+            }
+        }
+    }
+
+    /// Change the pixel at an index to a color as `0xAARRGGBB`.
+    pub fn set(&mut self, index: usize, color: u32) {
+        self.fill(index..index + 1, color);
+    }
+
+    /// Change the pixels in a range of indices to a color as `0xAARRGGBB`.
+    pub fn fill(&mut self, range: Range<usize>, color: u32) {
+        match self {
+            PixelBuffer::Argb32(buffer) => buffer[range].fill(color),
+            PixelBuffer::Rgba32(buffer) => {
+                let [a, r, g, b] = color.to_be_bytes();
+
+                buffer[range].fill(u32::from_ne_bytes([r, g, b, a]))
+            }
+            PixelBuffer::Bgra32(buffer) => {
+                buffer[range].fill(u32::from_ne_bytes(color.to_le_bytes()))
+            }
+            PixelBuffer::Rgb565(buffer) => {
+                // Round every channel to the nearest value with fewer bits
+                let channel =
+                    |shift: u32, max: u32| (((color >> shift) & 0xFF) * max + 0x7F) / 0xFF;
+                let pixel = (channel(16, 0x1F) << 11) | (channel(8, 0x3F) << 5) | channel(0, 0x1F);
+
+                buffer[range].fill(pixel as u16)
+            }
+        }
+    }
+
+    /// Borrow the buffer again for a shorter lifetime.
+    pub fn reborrow(&mut self) -> PixelBuffer<'_> {
+        match self {
+            PixelBuffer::Argb32(buffer) => PixelBuffer::Argb32(buffer),
+            PixelBuffer::Rgba32(buffer) => PixelBuffer::Rgba32(buffer),
+            PixelBuffer::Bgra32(buffer) => PixelBuffer::Bgra32(buffer),
+            PixelBuffer::Rgb565(buffer) => PixelBuffer::Rgb565(buffer),
+        }
+    }
+}
+
+impl<'a> From<&'a mut [u32]> for PixelBuffer<'a> {
+    fn from(buffer: &'a mut [u32]) -> Self {
+        PixelBuffer::Argb32(buffer)
+    }
+}
+
+impl<'a> From<&'a mut Vec<u32>> for PixelBuffer<'a> {
+    fn from(buffer: &'a mut Vec<u32>) -> Self {
+        PixelBuffer::Argb32(buffer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Write a color to a single pixel of the buffer and read it back.
+    fn round_trip(mut buffer: PixelBuffer, color: u32) -> u32 {
+        buffer.set(0, color);
+
+        buffer.get(0)
+    }
+
+    #[test]
+    fn byte_order() {
+        let mut pixel = [0u32];
+        PixelBuffer::Argb32(&mut pixel).set(0, 0x80_11_22_33);
+        assert_eq!(pixel[0], 0x80_11_22_33);
+
+        PixelBuffer::Rgba32(&mut pixel).set(0, 0x80_11_22_33);
+        assert_eq!(pixel[0].to_ne_bytes(), [0x11, 0x22, 0x33, 0x80]);
+
+        PixelBuffer::Bgra32(&mut pixel).set(0, 0x80_11_22_33);
+        assert_eq!(pixel[0].to_ne_bytes(), [0x33, 0x22, 0x11, 0x80]);
+    }
+
+    #[test]
+    fn round_trip_32_bits() {
+        for &color in [0, 0xFF_FF_FF_FF, 0x80_11_22_33, 0x01_FE_7F_80].iter() {
+            let mut pixel = [0u32];
+            assert_eq!(round_trip(PixelBuffer::Argb32(&mut pixel), color), color);
+            assert_eq!(round_trip(PixelBuffer::Rgba32(&mut pixel), color), color);
+            assert_eq!(round_trip(PixelBuffer::Bgra32(&mut pixel), color), color);
+        }
+    }
+
+    #[test]
+    fn round_trip_rgb565() {
+        let mut pixel = [0u16];
+
+        // Every 16-bit pixel stays the same when it's read and written again
+        for value in 0..=0xFFFF {
+            pixel[0] = value;
+            let color = PixelBuffer::Rgb565(&mut pixel).get(0);
+            PixelBuffer::Rgb565(&mut pixel).set(0, color);
+            assert_eq!(pixel[0], value);
+        }
+
+        // Black and white are exact and the alpha is opaque
+        assert_eq!(
+            round_trip(PixelBuffer::Rgb565(&mut pixel), 0),
+            0xFF_00_00_00
+        );
+        assert_eq!(
+            round_trip(PixelBuffer::Rgb565(&mut pixel), 0x00_FF_FF_FF),
+            0xFF_FF_FF_FF
+        );
+
+        // The channels are rounded to the nearest value
+        for channel in 0..=0xFF {
+            let color = round_trip(
+                PixelBuffer::Rgb565(&mut pixel),
+                channel << 16 | channel << 8,
+            );
+            let (red, green) = ((color >> 16) & 0xFF, (color >> 8) & 0xFF);
+            assert!((red as i32 - channel as i32).abs() <= 4);
+            assert!((green as i32 - channel as i32).abs() <= 2);
+        }
+        assert_eq!(
+            round_trip(PixelBuffer::Rgb565(&mut pixel), 0x05_00_00),
+            0xFF_08_00_00
+        );
+        assert_eq!(
+            round_trip(PixelBuffer::Rgb565(&mut pixel), 0x03_00_00),
+            0xFF_00_00_00
+        );
+    }
+}
@@ -0,0 +1,131 @@
+use std::cmp;
+
+/// A rectangle in pixels where the position is the top left corner.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+pub struct Rect {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+impl Rect {
+    /// Create a new rectangle.
+    pub fn new(x: i32, y: i32, width: i32, height: i32) -> Self {
+        Rect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Create a new rectangle from a position and a size tuple.
+    pub fn from_pos_size(pos: (i32, i32), size: (i32, i32)) -> Self {
+        Rect::new(pos.0, pos.1, size.0, size.1)
+    }
+
+    /// The top left corner.
+    pub fn pos(&self) -> (i32, i32) {
+        (self.x, self.y)
+    }
+
+    /// The width and height.
+    pub fn size(&self) -> (i32, i32) {
+        (self.width, self.height)
+    }
+
+    /// The first column right of the rectangle.
+    pub fn right(&self) -> i32 {
+        self.x + self.width
+    }
+
+    /// The first row below the rectangle.
+    pub fn bottom(&self) -> i32 {
+        self.y + self.height
+    }
+
+    /// Whether the rectangle doesn't cover any pixels.
+    pub fn is_empty(&self) -> bool {
+        self.width <= 0 || self.height <= 0
+    }
+
+    /// Whether a point lies inside the rectangle.
+    pub fn contains(&self, point: (i32, i32)) -> bool {
+        point.0 >= self.x && point.1 >= self.y && point.0 < self.right() && point.1 < self.bottom()
+    }
+
+    /// The area covered by both rectangles, the result is empty if they don't overlap.
+    pub fn intersection(&self, other: Rect) -> Rect {
+        let x = cmp::max(self.x, other.x);
+        let y = cmp::max(self.y, other.y);
+        let right = cmp::min(self.right(), other.right());
+        let bottom = cmp::min(self.bottom(), other.bottom());
+
+        Rect::new(x, y, cmp::max(right - x, 0), cmp::max(bottom - y, 0))
+    }
+
+    /// Move the rectangle.
+    pub fn translate(&self, x: i32, y: i32) -> Rect {
+        Rect::new(self.x + x, self.y + y, self.width, self.height)
+    }
+
+    /// Expand the rectangle on all sides, a negative amount shrinks it.
+    pub fn grow(&self, amount: i32) -> Rect {
+        Rect::new(
+            self.x - amount,
+            self.y - amount,
+            cmp::max(self.width + amount * 2, 0),
+            cmp::max(self.height + amount * 2, 0),
+        )
+    }
+}
+
+/// Distances in pixels from the edges of a rectangle towards its center.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+pub struct Insets {
+    pub left: i32,
+    pub top: i32,
+    pub right: i32,
+    pub bottom: i32,
+}
+
+impl Insets {
+    /// Create new insets.
+    pub fn new(left: i32, top: i32, right: i32, bottom: i32) -> Self {
+        Insets {
+            left,
+            top,
+            right,
+            bottom,
+        }
+    }
+
+    /// Create new insets which are the same on every side.
+    pub fn uniform(inset: i32) -> Self {
+        Insets::new(inset, inset, inset, inset)
+    }
+}
+
+/// Where something is placed inside a larger area along a single axis.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq, Hash)]
+pub enum Alignment {
+    /// At the left or top.
+    #[default]
+    Start,
+    /// In the middle.
+    Center,
+    /// At the right or bottom.
+    End,
+}
+
+impl Alignment {
+    /// The offset of something with a size placed inside an available space.
+    pub fn offset(self, available: i32, size: i32) -> i32 {
+        match self {
+            Alignment::Start => 0,
+            Alignment::Center => (available - size) / 2,
+            Alignment::End => available - size,
+        }
+    }
+}
@@ -1,14 +1,18 @@
-use blit::{BlitBuffer, Color};
+use blit::BlitBuffer;
+#[cfg(feature = "file-loading")]
+use blit::Color;
 use std::{error::Error, fmt};
 
-#[cfg(feature = "file-loading")]
-use blit::BlitExt;
-#[cfg(feature = "file-loading")]
-use image;
 #[cfg(feature = "file-loading")]
 use std::path::Path;
 
+use super::bitmap::Bitmap;
+use super::controls::ControlType;
 use super::font::*;
+use super::nine_slice::NineSlice;
+use super::rect::Insets;
+use super::store::{Key, Store};
+use super::theme::Theme;
 
 /// An error type for when a image has the wrong extension.
 #[derive(Debug, Clone)]
@@ -31,45 +35,50 @@ impl Error for InvalidImageFormat {
 }
 
 /// A newtype for handling sprites objects externally by reference.
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
-pub struct SpriteRef(usize);
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct SpriteRef(Key);
 
 /// A newtype for handling font objects externally by reference.
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
-pub struct FontRef(usize);
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct FontRef(Key);
+
+/// A newtype for handling nine-slice sprite objects externally by reference.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct NineSliceRef(Key);
 
 /// A internal handler of static resources such as sprites and fonts.
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct Resources {
-    sprites: Vec<BlitBuffer>,
-    fonts: Vec<Font>,
+    sprites: Store<Bitmap>,
+    fonts: Store<Font>,
+    nine_slices: Store<NineSlice>,
+    default_font: FontRef,
+    theme: Theme,
 }
 
 impl Resources {
     pub fn new() -> Self {
-        let mut fonts = Vec::new();
+        let mut fonts = Store::new();
 
         // Load the default font
         let default_font_buffer =
             BlitBuffer::from_memory(include_bytes!("../resources/ArtosSans.png.blit")).unwrap();
 
-        let default_font_settings = FontSettings {
-            start: '!',
-            char_size: (9, 9),
-            leading_offset: 2,
-            mask_color: Color::from_u32(0xFF_00_FF),
-        };
-        fonts.push(Font::new(default_font_buffer, default_font_settings));
+        let default_font =
+            FontRef(fonts.insert(Font::new(default_font_buffer, FontSettings::default())));
 
         Resources {
             fonts,
-            sprites: Vec::new(),
+            sprites: Store::new(),
+            nine_slices: Store::new(),
+            default_font,
+            theme: Theme::new(),
         }
     }
 
     /// Return the default font loaded from the `assets/` folder and parsed by `build.rs`.
     pub fn default_font(&self) -> FontRef {
-        FontRef(0)
+        self.default_font
     }
 
     /// Load image from a path.
@@ -79,7 +88,8 @@ impl Resources {
     /// Accepts both PNG & BlitBuffer images which should have the `.png` and `.blit` extension respectively.
     ///
     /// The mask color is the color that will be used as alpha in the sprite, a common color to use
-    /// for this is `0xFF_00_FF`.
+    /// for this is `0xFF_00_FF`. PNG images with an alpha channel are blended with what's below
+    /// them.
     ///
     /// Returns a reference to the image.
     #[cfg(feature = "file-loading")]
@@ -91,31 +101,26 @@ impl Resources {
     where
         P: AsRef<Path>,
     {
-        let index = self.sprites.len();
-
-        let buffer = Resources::load_blitbuffer(path.as_ref(), mask_color)?;
-        self.sprites.push(buffer);
+        let buffer = Resources::load_bitmap(path.as_ref(), mask_color)?;
 
-        Ok(SpriteRef(index))
+        Ok(SpriteRef(self.sprites.insert(buffer)))
     }
 
     /// Load image from serialized memory.
     pub fn load_sprite_from_memory(&mut self, buffer: &[u8]) -> Result<SpriteRef, Box<dyn Error>> {
-        let index = self.sprites.len();
-
         let blitbuffer = BlitBuffer::from_memory(buffer)?;
-        self.sprites.push(blitbuffer);
 
-        Ok(SpriteRef(index))
+        Ok(SpriteRef(self.sprites.insert(blitbuffer.into())))
+    }
+
+    /// Add a sprite from a bitmap, for example one with translucent pixels.
+    pub fn load_sprite_from_bitmap(&mut self, bitmap: Bitmap) -> SpriteRef {
+        SpriteRef(self.sprites.insert(bitmap))
     }
 
     /// Retrieves the sprite if it exists.
-    pub fn get_sprite(&self, sprite_ref: SpriteRef) -> Option<&BlitBuffer> {
-        if sprite_ref.0 < self.sprites.len() {
-            Some(&self.sprites[sprite_ref.0])
-        } else {
-            None
-        }
+    pub fn get_sprite(&self, sprite_ref: SpriteRef) -> Option<&Bitmap> {
+        self.sprites.get(sprite_ref.0)
     }
 
     /// Load font image from a path. Accepts both PNG & BlitBuffer images which should have the `.png`
@@ -133,12 +138,9 @@ impl Resources {
     where
         P: AsRef<Path>,
     {
-        let index = self.fonts.len();
-
-        let buffer = Resources::load_blitbuffer(path.as_ref(), settings.mask_color)?;
-        self.fonts.push(Font::new(buffer, settings));
+        let buffer = Resources::load_bitmap(path.as_ref(), settings.mask_color)?;
 
-        Ok(FontRef(index))
+        Ok(FontRef(self.fonts.insert(Font::new(buffer, settings))))
     }
 
     /// Load image from serialized memory.
@@ -147,47 +149,115 @@ impl Resources {
         buffer: &[u8],
         settings: FontSettings,
     ) -> Result<FontRef, Box<dyn Error>> {
-        let index = self.sprites.len();
-
         let blitbuffer = BlitBuffer::from_memory(buffer)?;
-        self.fonts.push(Font::new(blitbuffer, settings));
 
-        Ok(FontRef(index))
+        Ok(FontRef(self.fonts.insert(Font::new(blitbuffer, settings))))
     }
 
     /// Retrieves the font if it exists.
     pub fn get_font(&self, font_ref: FontRef) -> Option<&Font> {
-        if font_ref.0 < self.fonts.len() {
-            Some(&self.fonts[font_ref.0])
-        } else {
-            None
-        }
+        self.fonts.get(font_ref.0)
+    }
+
+    /// Retrieves the font of the theme for the control type, or the font if the theme has none.
+    pub fn get_themed_font(&self, control_type: ControlType, font_ref: FontRef) -> Option<&Font> {
+        self.get_font(self.theme.font(control_type).unwrap_or(font_ref))
+    }
+
+    /// Load nine-slice image from a path. Accepts both PNG & BlitBuffer images which should have
+    /// the `.png` and `.blit` extension respectively.
+    ///
+    /// This function is only available when the `"file-loading"` feature is enabled.
+    ///
+    /// Returns a reference to the nine-slice sprite.
+    #[cfg(feature = "file-loading")]
+    pub fn load_nine_slice_from_file<P>(
+        &mut self,
+        path: P,
+        mask_color: Color,
+        insets: Insets,
+    ) -> Result<NineSliceRef, Box<dyn Error>>
+    where
+        P: AsRef<Path>,
+    {
+        let buffer = Resources::load_bitmap(path.as_ref(), mask_color)?;
+
+        Ok(NineSliceRef(
+            self.nine_slices.insert(NineSlice::new(buffer, insets)),
+        ))
+    }
+
+    /// Load nine-slice image from serialized memory.
+    pub fn load_nine_slice_from_memory(
+        &mut self,
+        buffer: &[u8],
+        insets: Insets,
+    ) -> Result<NineSliceRef, Box<dyn Error>> {
+        let blitbuffer = BlitBuffer::from_memory(buffer)?;
+
+        Ok(NineSliceRef(
+            self.nine_slices.insert(NineSlice::new(blitbuffer, insets)),
+        ))
+    }
+
+    /// Retrieves the nine-slice sprite if it exists.
+    pub fn get_nine_slice(&self, nine_slice_ref: NineSliceRef) -> Option<&NineSlice> {
+        self.nine_slices.get(nine_slice_ref.0)
+    }
+
+    /// The theme used to draw the controls.
+    pub fn theme(&self) -> &Theme {
+        &self.theme
+    }
+
+    /// The theme used to draw the controls, to change it.
+    pub fn theme_mut(&mut self) -> &mut Theme {
+        &mut self.theme
     }
 
     /// Load a encoded image from a file.
     ///
+    /// The pixels with the mask color are transparent, PNG images can also have an alpha channel.
+    ///
     /// This function is only available when the `"file-loading"` feature is enabled.
     #[cfg(feature = "file-loading")]
-    pub fn load_blitbuffer(path: &Path, mask_color: Color) -> Result<BlitBuffer, Box<dyn Error>> {
+    pub fn load_bitmap(path: &Path, mask_color: Color) -> Result<Bitmap, Box<dyn Error>> {
         let ext = path
             .extension()
             .and_then(|s| s.to_str())
             .map_or("".to_string(), |s| s.to_ascii_lowercase());
 
-        let buffer = match &ext[..] {
-            "blit" => BlitBuffer::open(path)?,
+        let bitmap = match &ext[..] {
+            "blit" => BlitBuffer::open(path)?.into(),
             "png" => {
-                // Open the image from the path and convert it to a blit buffer
-                let img = image::open(path)?;
-                let rgb = img
-                    .as_rgb8()
-                    .expect("Image is not of a valid type, consider removing the alpha channel");
-
-                rgb.to_blit_buffer(mask_color)
+                // Images without an alpha channel are converted to opaque pixels
+                let img = image::open(path)?.to_rgba8();
+                let mask = mask_color.u32() & 0xFF_FF_FF;
+                let pixels: Vec<u32> = img
+                    .pixels()
+                    .map(|pixel| {
+                        let [r, g, b, a] = pixel.0;
+                        let rgb = (u32::from(r) << 16) | (u32::from(g) << 8) | u32::from(b);
+
+                        if rgb == mask {
+                            0
+                        } else {
+                            (u32::from(a) << 24) | rgb
+                        }
+                    })
+                    .collect();
+
+                Bitmap::from_argb(&pixels, img.width() as i32)
             }
             _ => return Err(Box::new(InvalidImageFormat)),
         };
 
-        Ok(buffer)
+        Ok(bitmap)
+    }
+}
+
+impl Default for Resources {
+    fn default() -> Self {
+        Resources::new()
     }
 }
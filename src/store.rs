@@ -0,0 +1,164 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Every store gets a unique identifier so keys from other stores can be detected.
+static NEXT_STORE_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// A generational index into a `Store`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Key {
+    store: usize,
+    index: usize,
+    generation: u32,
+}
+
+#[derive(Debug)]
+struct Slot<T> {
+    generation: u32,
+    value: Option<T>,
+}
+
+/// A slot map where removed slots are reused, the generation of a slot is increased every time
+/// it's emptied so old keys pointing to it are invalid.
+#[derive(Debug)]
+pub struct Store<T> {
+    id: usize,
+    slots: Vec<Slot<T>>,
+    free: Vec<usize>,
+}
+
+impl<T> Store<T> {
+    pub fn new() -> Self {
+        Store {
+            id: NEXT_STORE_ID.fetch_add(1, Ordering::Relaxed),
+            slots: Vec::new(),
+            free: Vec::new(),
+        }
+    }
+
+    /// Add a value and return the key to retrieve it with.
+    pub fn insert(&mut self, value: T) -> Key {
+        let index = match self.free.pop() {
+            Some(index) => {
+                self.slots[index].value = Some(value);
+
+                index
+            }
+            None => {
+                self.slots.push(Slot {
+                    generation: 0,
+                    value: Some(value),
+                });
+
+                self.slots.len() - 1
+            }
+        };
+
+        Key {
+            store: self.id,
+            index,
+            generation: self.slots[index].generation,
+        }
+    }
+
+    /// Retrieve the value if the key is still valid.
+    pub fn get(&self, key: Key) -> Option<&T> {
+        self.slot(key).and_then(|slot| slot.value.as_ref())
+    }
+
+    /// Retrieve the value mutably if the key is still valid.
+    pub fn get_mut(&mut self, key: Key) -> Option<&mut T> {
+        if !self.is_valid(key) {
+            return None;
+        }
+
+        self.slots[key.index].value.as_mut()
+    }
+
+    /// Remove the value, the key and all copies of it will be invalid afterwards.
+    pub fn remove(&mut self, key: Key) -> Option<T> {
+        if !self.is_valid(key) {
+            return None;
+        }
+
+        let slot = &mut self.slots[key.index];
+        let value = slot.value.take();
+        slot.generation = slot.generation.wrapping_add(1);
+        self.free.push(key.index);
+
+        value
+    }
+
+    /// Remove all values, invalidating all keys.
+    pub fn clear(&mut self) {
+        for (index, slot) in self.slots.iter_mut().enumerate() {
+            if slot.value.take().is_some() {
+                slot.generation = slot.generation.wrapping_add(1);
+                self.free.push(index);
+            }
+        }
+    }
+
+    fn is_valid(&self, key: Key) -> bool {
+        self.get(key).is_some()
+    }
+
+    fn slot(&self, key: Key) -> Option<&Slot<T>> {
+        if key.store != self.id {
+            return None;
+        }
+
+        self.slots
+            .get(key.index)
+            .filter(|slot| slot.generation == key.generation)
+    }
+}
+
+impl<T> Default for Store<T> {
+    fn default() -> Self {
+        Store::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn removed_keys_are_invalid() {
+        let mut store = Store::new();
+        let first = store.insert(1);
+        assert_eq!(store.remove(first), Some(1));
+        assert_eq!(store.get(first), None);
+        assert_eq!(store.remove(first), None);
+
+        // The slot is reused with a new generation
+        let second = store.insert(2);
+        assert_eq!(second.index, first.index);
+        assert_eq!(store.get(first), None);
+        assert_eq!(store.get_mut(first), None);
+        assert_eq!(store.get(second), Some(&2));
+    }
+
+    #[test]
+    fn cleared_keys_are_invalid() {
+        let mut store = Store::new();
+        let key = store.insert(1);
+        store.clear();
+        assert_eq!(store.get(key), None);
+
+        let key = store.insert(2);
+        assert_eq!(store.get(key), Some(&2));
+    }
+
+    #[test]
+    fn keys_of_other_stores_are_invalid() {
+        let mut store = Store::new();
+        let mut other = Store::new();
+        let key = store.insert(1);
+        other.insert(2);
+
+        assert_eq!(other.get(key), None);
+        assert_eq!(other.remove(key), None);
+        assert_eq!(store.get(key), Some(&1));
+    }
+}
@@ -0,0 +1,255 @@
+use blit::Color;
+use std::collections::HashMap;
+
+use super::canvas::Canvas;
+use super::controls::{ButtonState, ControlType};
+use super::rect::Rect;
+use super::resources::{FontRef, NineSliceRef, Resources};
+
+/// The state of a control a style is used for.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum StyleState {
+    Normal,
+    /// The mouse is hovering over the control.
+    Hover,
+    /// The control is pressed or dragged.
+    Pressed,
+    /// The control receives the keyboard input.
+    Focused,
+    /// The control is disabled with `Gui::set_enabled`.
+    Disabled,
+}
+
+impl From<ButtonState> for StyleState {
+    fn from(state: ButtonState) -> Self {
+        match state {
+            ButtonState::Normal => StyleState::Normal,
+            ButtonState::Hover => StyleState::Hover,
+            ButtonState::Pressed => StyleState::Pressed,
+            ButtonState::Disabled => StyleState::Disabled,
+        }
+    }
+}
+
+/// How the fill color of a control is changed in a state.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Shade {
+    /// The color is used as it is.
+    None,
+    /// The bits are added to the color, which lightens it.
+    Lighten(u32),
+    /// Only the bits are kept of the color, which darkens it.
+    Darken(u32),
+    /// The color is converted to the gray with the same brightness.
+    Desaturate,
+}
+
+impl Shade {
+    /// Change the color.
+    pub fn apply(self, color: Color) -> Color {
+        let color = color.u32();
+
+        Color::from_u32(match self {
+            Shade::None => color,
+            Shade::Lighten(bits) => color | bits,
+            Shade::Darken(bits) => color & bits,
+            Shade::Desaturate => {
+                let (r, g, b) = ((color >> 16) & 0xFF, (color >> 8) & 0xFF, color & 0xFF);
+                let gray = (r * 30 + g * 59 + b * 11) / 100;
+
+                (gray << 16) | (gray << 8) | gray
+            }
+        })
+    }
+}
+
+/// How a control is drawn in a state.
+///
+/// Only controls which are drawn by an algorithm use the style, controls with sprites look like
+/// their sprites.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Style {
+    /// The fill color, with `None` the color the control is created with is used.
+    pub color: Option<Color>,
+    /// How the fill color is changed.
+    pub shade: Shade,
+    /// The color of the border.
+    pub border_color: Color,
+    /// The width of the border in pixels, `0` draws no border.
+    pub border_width: i32,
+    /// The color text is tinted with, with `None` the text has the colors of the font.
+    pub text_color: Option<Color>,
+    /// A nine-slice sprite which is drawn instead of the fill color and the border, the edges
+    /// and the center are repeated to fill the control.
+    pub nine_slice: Option<NineSliceRef>,
+}
+
+impl Style {
+    /// The fill color for a control created with a color.
+    pub fn fill_color(&self, color: Color) -> Color {
+        self.shade.apply(self.color.unwrap_or(color))
+    }
+
+    /// Draw a rectangle filled with the fill color and a border around it.
+    pub fn draw_rect(&self, canvas: &mut Canvas, res: &Resources, rect: Rect, color: Color) {
+        if let Some(nine_slice_ref) = self.nine_slice {
+            res.get_nine_slice(nine_slice_ref)
+                .unwrap()
+                .draw(canvas, rect);
+
+            return;
+        }
+
+        canvas.fill_rect(rect, self.fill_color(color));
+        self.draw_border(canvas, rect);
+    }
+
+    /// Draw the border on the inside of a rectangle.
+    pub fn draw_border(&self, canvas: &mut Canvas, rect: Rect) {
+        for inset in 0..self.border_width {
+            canvas.stroke_rect(rect.grow(-inset), self.border_color);
+        }
+    }
+}
+
+impl Default for Style {
+    /// A black border of a single pixel around the color of the control.
+    fn default() -> Self {
+        Style {
+            color: None,
+            shade: Shade::None,
+            border_color: Color::from_u32(0),
+            border_width: 1,
+            text_color: None,
+            nine_slice: None,
+        }
+    }
+}
+
+/// The styles and fonts of all controls, registered on the `Gui` with `Gui::set_theme`.
+///
+/// Styles which are not set fall back to the default look of the control type, so a theme only
+/// needs to contain what's different.
+///
+/// ```rust
+/// use direct_gui::controls::ControlType;
+/// use direct_gui::*;
+///
+/// let mut gui = Gui::new((800, 600));
+///
+/// // Light blue buttons with a thick border that turn white when hovered
+/// let button = Style {
+///     color: Some(Color::from_u32(0x88_88_FF)),
+///     border_width: 2,
+///     ..Style::default()
+/// };
+/// gui.set_theme(
+///     Theme::new()
+///         .with_style(ControlType::Button, StyleState::Normal, button)
+///         .with_style(
+///             ControlType::Button,
+///             StyleState::Hover,
+///             Style {
+///                 shade: Shade::Lighten(0xFF_FF_FF),
+///                 ..button
+///             },
+///         ),
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Theme {
+    styles: HashMap<(ControlType, StyleState), Style>,
+    fonts: HashMap<ControlType, FontRef>,
+}
+
+impl Theme {
+    /// Create a theme with the default look for every control.
+    pub fn new() -> Self {
+        Theme::default()
+    }
+
+    /// Map the style of a control type in a state.
+    pub fn with_style(
+        mut self,
+        control_type: ControlType,
+        state: StyleState,
+        style: Style,
+    ) -> Self {
+        self.set_style(control_type, state, style);
+
+        self
+    }
+
+    /// Change the style of a control type in a state.
+    pub fn set_style(&mut self, control_type: ControlType, state: StyleState, style: Style) {
+        self.styles.insert((control_type, state), style);
+    }
+
+    /// The style of a control type in a state.
+    pub fn style(&self, control_type: ControlType, state: StyleState) -> Style {
+        self.styles
+            .get(&(control_type, state))
+            .cloned()
+            .unwrap_or_else(|| default_style(control_type, state))
+    }
+
+    /// Map the font of a control type, it's used instead of the font the controls are created
+    /// with.
+    pub fn with_font(mut self, control_type: ControlType, font_ref: FontRef) -> Self {
+        self.set_font(control_type, Some(font_ref));
+
+        self
+    }
+
+    /// Change the font of a control type, with `None` the controls use their own font.
+    pub fn set_font(&mut self, control_type: ControlType, font_ref: Option<FontRef>) {
+        match font_ref {
+            Some(font_ref) => self.fonts.insert(control_type, font_ref),
+            None => self.fonts.remove(&control_type),
+        };
+    }
+
+    /// The font of a control type, `None` when the controls use their own font.
+    pub fn font(&self, control_type: ControlType) -> Option<FontRef> {
+        self.fonts.get(&control_type).cloned()
+    }
+}
+
+/// How the controls look without a theme.
+fn default_style(control_type: ControlType, state: StyleState) -> Style {
+    let style = match control_type {
+        ControlType::TextInput => {
+            return Style {
+                color: Some(Color::from_u32(0x22_22_22)),
+                border_color: Color::from_u32(match state {
+                    StyleState::Focused => 0xFF_FF_FF,
+                    _ => 0x88_88_88,
+                }),
+                ..Style::default()
+            }
+        }
+        // The border of the thumb is white when the slider is focused
+        ControlType::Slider | ControlType::Scrollbar if state == StyleState::Focused => Style {
+            border_color: Color::from_u32(0xFF_FF_FF),
+            ..Style::default()
+        },
+        _ => Style::default(),
+    };
+
+    match state {
+        StyleState::Hover => Style {
+            shade: Shade::Lighten(0x44_44_44),
+            ..style
+        },
+        StyleState::Pressed => Style {
+            shade: Shade::Darken(0xAA_AA_AA),
+            ..style
+        },
+        StyleState::Disabled => Style {
+            shade: Shade::Desaturate,
+            text_color: Some(Color::from_u32(0x80_80_80)),
+            ..style
+        },
+        StyleState::Normal | StyleState::Focused => style,
+    }
+}